@@ -1,14 +1,20 @@
+pub mod lint;
+pub mod merc;
+
 use crate::character::{CharId, CharacterData};
-use crate::object::ObjectDef;
+use crate::object::{ObjectDef, ObjectId};
 use crate::room::{RoomDef, RoomId};
+use crate::weather::Weather;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 
 #[derive(Debug, Deserialize, Default, Serialize)]
 pub struct AreaDef {
+    #[serde(skip)]
+    pub file_name: String,
     name: String,
     author: String,
     pub npcs: Vec<CharacterData>,
@@ -28,20 +34,36 @@ impl AreaDef {
     pub fn extract_npcs(&mut self) -> Vec<CharacterData> {
         std::mem::take(&mut self.npcs)
     }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        // Going through a `Value` lets the serializer put the plain fields of each table
+        // ahead of its sub-tables, which TOML requires and our structs don't guarantee.
+        let value = toml::Value::try_from(self)?;
+        toml::to_string_pretty(&value)
+    }
 }
 
+// How many ticks pass between area resets
+const RESET_AGE: u32 = 15;
+
 #[derive(Default, Debug)]
 pub struct Area {
     name: String,
     author: String,
     age: u32,
+    pub file_name: String,
     pub rooms: Vec<RoomId>,
+    pub objects: Vec<ObjectId>,
+    pub npcs: Vec<CharId>,
+    pub weather: Weather,
 }
 
 #[derive(Debug)]
 pub enum AreaLoadError {
     IO(std::io::Error),
     Parse(toml::de::Error),
+    // Not the name of any area file
+    Unknown(String),
 }
 
 impl From<std::io::Error> for AreaLoadError {
@@ -56,20 +78,97 @@ impl From<toml::de::Error> for AreaLoadError {
     }
 }
 
+#[derive(Debug)]
+pub enum AreaSaveError {
+    IO(std::io::Error),
+    Serialize(toml::ser::Error),
+}
+
+impl From<std::io::Error> for AreaSaveError {
+    fn from(e: std::io::Error) -> AreaSaveError {
+        AreaSaveError::IO(e)
+    }
+}
+
+impl From<toml::ser::Error> for AreaSaveError {
+    fn from(e: toml::ser::Error) -> AreaSaveError {
+        AreaSaveError::Serialize(e)
+    }
+}
+
 impl Area {
+    /// Ages the area by a tick. Returns true when it's time for it to reset.
+    pub fn age(&mut self) -> bool {
+        self.age += 1;
+        if self.age < RESET_AGE {
+            return false;
+        }
+        self.age = 0;
+        true
+    }
+
     pub fn load<P: AsRef<Path>>(name: P) -> Result<AreaDef, AreaLoadError> {
-        let path = Path::new("areas").join(name).with_extension("toml");
+        let path = Path::new("areas").join(&name).with_extension("toml");
         let mut s = String::new();
         let mut f = File::open(path)?;
         f.read_to_string(&mut s)?;
-        let area: AreaDef = toml::from_str(&s)?;
+        let mut area: AreaDef = toml::from_str(&s)?;
+        area.file_name = name.as_ref().to_string_lossy().into_owned();
         Ok(area)
     }
 
+    pub fn list() -> std::io::Result<Vec<String>> {
+        let mut names = vec![];
+        for entry in std::fs::read_dir("areas")? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn save(area_def: &AreaDef) -> Result<(), AreaSaveError> {
+        let s = area_def.to_toml()?;
+        let path = Path::new("areas")
+            .join(&area_def.file_name)
+            .with_extension("toml");
+        let tmp_path = path.with_extension("toml.tmp");
+        let mut f = File::create(&tmp_path)?;
+        f.write_all(s.as_bytes())?;
+        f.sync_data()?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    pub fn to_prototype(
+        &self,
+        npcs: Vec<CharacterData>,
+        objects: Vec<ObjectDef>,
+        rooms: Vec<RoomDef>,
+    ) -> AreaDef {
+        AreaDef {
+            file_name: self.file_name.clone(),
+            name: self.name.clone(),
+            author: self.author.clone(),
+            npcs,
+            objects,
+            rooms,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn from_prototype(area_def: AreaDef) -> Area {
         Area {
             name: area_def.name,
             author: area_def.author,
+            file_name: area_def.file_name,
             rooms: Vec::with_capacity(area_def.rooms.len()),
             ..Default::default()
         }
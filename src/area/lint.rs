@@ -0,0 +1,691 @@
+use super::AreaDef;
+use crate::character::CharId;
+use crate::object::{ObjectId, Portal};
+use crate::room::{RoomFlag, RoomId};
+use ahash::RandomState;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+
+// Anything wider than this wraps on an 80 column terminal once the
+// connection's line ending is added.
+pub const MAX_LINE_WIDTH: usize = 79;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Lint {
+    DuplicateRoom(RoomId),
+    DuplicateObject(ObjectId),
+    DuplicateNpc(CharId),
+    DanglingExit {
+        room: RoomId,
+        dir: String,
+        to: RoomId,
+    },
+    AsymmetricExit {
+        room: RoomId,
+        dir: String,
+        to: RoomId,
+    },
+    UnreachableRoom(RoomId),
+    MissingStartRoom,
+    UnreferencedObject(ObjectId),
+    MissingObject {
+        room: RoomId,
+        object: ObjectId,
+    },
+    MisplacedNpc {
+        npc: CharId,
+        room: RoomId,
+    },
+    NpcInNoMobRoom {
+        npc: CharId,
+        room: RoomId,
+    },
+    MissingKey {
+        room: RoomId,
+        dir: String,
+        key: ObjectId,
+    },
+    MissingStock {
+        npc: CharId,
+        object: ObjectId,
+    },
+    DanglingPortal {
+        object: ObjectId,
+        to: RoomId,
+    },
+    MissingPortalArea {
+        object: ObjectId,
+        area: String,
+    },
+    UnpairedPortal {
+        object: ObjectId,
+        pair: ObjectId,
+    },
+    ObjectMissingKeywords(ObjectId),
+    NpcMissingKeywords(CharId),
+    ExtraDescriptionMissingKeywords(RoomId),
+    OverlongDescription {
+        what: String,
+        line: usize,
+        width: usize,
+    },
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lint::DuplicateRoom(id) => write!(f, "room {} is defined more than once", id),
+            Lint::DuplicateObject(id) => write!(f, "object {} is defined more than once", id),
+            Lint::DuplicateNpc(id) => write!(f, "npc {} is defined more than once", id),
+            Lint::DanglingExit { room, dir, to } => write!(
+                f,
+                "room {}'s exit '{}' leads to nonexistant room {}",
+                room, dir, to
+            ),
+            Lint::AsymmetricExit { room, dir, to } => write!(
+                f,
+                "room {}'s exit '{}' to room {} has no matching exit back",
+                room, dir, to
+            ),
+            Lint::UnreachableRoom(id) => {
+                write!(f, "room {} can't be reached from the starting room", id)
+            }
+            Lint::MissingStartRoom => {
+                write!(f, "starting room {} doesn't exist", RoomId::default())
+            }
+            Lint::UnreferencedObject(id) => write!(f, "object {} is never loaded", id),
+            Lint::MissingObject { room, object } => {
+                write!(f, "room {} loads nonexistant object {}", room, object)
+            }
+            Lint::MisplacedNpc { npc, room } => {
+                write!(f, "npc {} is placed in nonexistant room {}", npc, room)
+            }
+            Lint::NpcInNoMobRoom { npc, room } => {
+                write!(f, "npc {} is placed in no-mob room {}", npc, room)
+            }
+            Lint::MissingKey { room, dir, key } => write!(
+                f,
+                "door {} of room {} needs nonexistant key {}",
+                dir, room, key
+            ),
+            Lint::MissingStock { npc, object } => {
+                write!(f, "npc {} sells nonexistant object {}", npc, object)
+            }
+            Lint::DanglingPortal { object, to } => {
+                write!(f, "portal {} leads to nonexistant room {}", object, to)
+            }
+            Lint::MissingPortalArea { object, area } => {
+                write!(f, "portal {} leads to nonexistant area {}", object, area)
+            }
+            Lint::UnpairedPortal { object, pair } => write!(
+                f,
+                "portal {} is paired with {}, which isn't a portal paired back",
+                object, pair
+            ),
+            Lint::ObjectMissingKeywords(id) => write!(f, "object {} has no keywords", id),
+            Lint::NpcMissingKeywords(id) => write!(f, "npc {} has no keywords", id),
+            Lint::ExtraDescriptionMissingKeywords(id) => {
+                write!(f, "room {} has an extra description with no keywords", id)
+            }
+            Lint::OverlongDescription { what, line, width } => write!(
+                f,
+                "{} line {} is {} columns wide (max {})",
+                what, line, width, MAX_LINE_WIDTH
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LintIssue {
+    pub area: String,
+    pub lint: Lint,
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.area, self.lint)
+    }
+}
+
+/// Checks a set of parsed areas against each other, so that ids and exits can
+/// refer from one area to another.
+pub fn lint_areas(areas: &[(String, AreaDef)]) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let mut room_ids = HashSet::with_hasher(RandomState::new());
+    let mut object_ids = HashSet::with_hasher(RandomState::new());
+    let mut npc_ids = HashSet::with_hasher(RandomState::new());
+
+    for (area, area_def) in areas {
+        let mut push = |lint| {
+            issues.push(LintIssue {
+                area: area.clone(),
+                lint,
+            })
+        };
+        for room in &area_def.rooms {
+            if !room_ids.insert(room.id) {
+                push(Lint::DuplicateRoom(room.id));
+            }
+        }
+        for obj in &area_def.objects {
+            if !object_ids.insert(obj.id) {
+                push(Lint::DuplicateObject(obj.id));
+            }
+            if obj.keywords.iter().all(|kw| kw.trim().is_empty()) {
+                push(Lint::ObjectMissingKeywords(obj.id));
+            }
+            if let Some(description) = &obj.description {
+                lint_text(
+                    &format!("object {} description", obj.id),
+                    description,
+                    &mut push,
+                );
+            }
+        }
+        for npc in &area_def.npcs {
+            if !npc_ids.insert(npc.id) {
+                push(Lint::DuplicateNpc(npc.id));
+            }
+            if npc.keywords.iter().all(|kw| kw.trim().is_empty()) {
+                push(Lint::NpcMissingKeywords(npc.id));
+            }
+            if let Some(description) = &npc.description {
+                lint_text(
+                    &format!("npc {} description", npc.id),
+                    description,
+                    &mut push,
+                );
+            }
+        }
+    }
+
+    for (area, area_def) in areas {
+        let mut push = |lint| {
+            issues.push(LintIssue {
+                area: area.clone(),
+                lint,
+            })
+        };
+        for room in &area_def.rooms {
+            lint_text(
+                &format!("room {} description", room.id),
+                &room.description,
+                &mut push,
+            );
+            for extra in &room.extra_descs {
+                if extra.keywords.iter().all(|kw| kw.trim().is_empty()) {
+                    push(Lint::ExtraDescriptionMissingKeywords(room.id));
+                }
+                lint_text(
+                    &format!("room {} extra description", room.id),
+                    &extra.description,
+                    &mut push,
+                );
+            }
+            for exit in room.exits.as_ref() {
+                let dir = exit.dir.leaving().to_string();
+                if let Some(key) = exit.key.filter(|key| !object_ids.contains(key)) {
+                    push(Lint::MissingKey {
+                        room: room.id,
+                        dir: dir.clone(),
+                        key,
+                    });
+                }
+                let destination = areas
+                    .iter()
+                    .flat_map(|(_, def)| &def.rooms)
+                    .find(|other| other.id == exit.to);
+                match destination {
+                    None => push(Lint::DanglingExit {
+                        room: room.id,
+                        dir,
+                        to: exit.to,
+                    }),
+                    Some(destination) => {
+                        if let Some(reverse) = exit.dir.reverse() {
+                            let returns = destination
+                                .exits
+                                .as_ref()
+                                .iter()
+                                .any(|back| back.dir == reverse && back.to == room.id);
+                            if !returns {
+                                push(Lint::AsymmetricExit {
+                                    room: room.id,
+                                    dir,
+                                    to: exit.to,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            for object in &room.load_objects {
+                if !object_ids.contains(object) {
+                    push(Lint::MissingObject {
+                        room: room.id,
+                        object: *object,
+                    });
+                }
+            }
+        }
+        for obj in &area_def.objects {
+            let loaded = areas
+                .iter()
+                .flat_map(|(_, def)| &def.rooms)
+                .any(|room| room.load_objects.contains(&obj.id));
+            let stocked = areas
+                .iter()
+                .flat_map(|(_, def)| &def.npcs)
+                .filter_map(|npc| npc.shop.as_ref())
+                .any(|shop| shop.stock.contains(&obj.id));
+            if !loaded && !stocked {
+                push(Lint::UnreferencedObject(obj.id));
+            }
+            if let Some(portal) = &obj.portal {
+                lint_portal(obj.id, portal, areas, &room_ids, &mut push);
+            }
+        }
+        for npc in &area_def.npcs {
+            if !room_ids.contains(&npc.in_room) {
+                push(Lint::MisplacedNpc {
+                    npc: npc.id,
+                    room: npc.in_room,
+                });
+            }
+            let no_mob = areas
+                .iter()
+                .flat_map(|(_, def)| &def.rooms)
+                .any(|room| room.id == npc.in_room && room.flags.contains(&RoomFlag::NoMob));
+            if no_mob {
+                push(Lint::NpcInNoMobRoom {
+                    npc: npc.id,
+                    room: npc.in_room,
+                });
+            }
+            for object in npc.shop.iter().flat_map(|shop| &shop.stock) {
+                if !object_ids.contains(object) {
+                    push(Lint::MissingStock {
+                        npc: npc.id,
+                        object: *object,
+                    });
+                }
+            }
+        }
+    }
+
+    issues.extend(lint_reachability(areas, &room_ids));
+    issues
+}
+
+fn lint_portal<F: FnMut(Lint)>(
+    object: ObjectId,
+    portal: &Portal,
+    areas: &[(String, AreaDef)],
+    room_ids: &HashSet<RoomId, RandomState>,
+    push: &mut F,
+) {
+    if let Some(to) = portal.to.filter(|to| !room_ids.contains(to)) {
+        push(Lint::DanglingPortal { object, to });
+    }
+    if let Some(area) = &portal.to_area {
+        if !areas.iter().any(|(file_name, _)| file_name == area) {
+            push(Lint::MissingPortalArea {
+                object,
+                area: area.clone(),
+            });
+        }
+    }
+    if let Some(pair) = portal.pair {
+        let pairs_back = areas
+            .iter()
+            .flat_map(|(_, def)| &def.objects)
+            .filter(|other| other.id == pair)
+            .filter_map(|other| other.portal.as_ref())
+            .any(|other| other.pair == Some(object));
+        if !pairs_back {
+            push(Lint::UnpairedPortal { object, pair });
+        }
+    }
+}
+
+// The rooms a portal loaded in a room can take someone to.
+fn portal_destinations(portal: &Portal, areas: &[(String, AreaDef)]) -> Vec<RoomId> {
+    let mut destinations: Vec<RoomId> = portal.to.into_iter().collect();
+    for (file_name, def) in areas {
+        if portal.to_area.as_ref() == Some(file_name) {
+            destinations.extend(def.rooms.iter().map(|room| room.id));
+        }
+        if let Some(pair) = portal.pair {
+            destinations.extend(
+                def.rooms
+                    .iter()
+                    .filter(|room| room.load_objects.contains(&pair))
+                    .map(|room| room.id),
+            );
+        }
+    }
+    destinations
+}
+
+fn lint_text<F: FnMut(Lint)>(what: &str, text: &str, push: &mut F) {
+    for (n, line) in text.lines().enumerate() {
+        let width = line.chars().count();
+        if width > MAX_LINE_WIDTH {
+            push(Lint::OverlongDescription {
+                what: what.to_string(),
+                line: n + 1,
+                width,
+            });
+        }
+    }
+}
+
+fn lint_reachability(
+    areas: &[(String, AreaDef)],
+    room_ids: &HashSet<RoomId, RandomState>,
+) -> Vec<LintIssue> {
+    let start = RoomId::default();
+    if !room_ids.contains(&start) {
+        return vec![LintIssue {
+            area: String::from("(world)"),
+            lint: Lint::MissingStartRoom,
+        }];
+    }
+
+    let mut seen = HashSet::with_hasher(RandomState::new());
+    let mut queue = VecDeque::new();
+    seen.insert(start);
+    queue.push_back(start);
+    while let Some(room_id) = queue.pop_front() {
+        let room = areas
+            .iter()
+            .flat_map(|(_, def)| &def.rooms)
+            .find(|room| room.id == room_id);
+        if let Some(room) = room {
+            let portals = room
+                .load_objects
+                .iter()
+                .filter_map(|id| {
+                    areas
+                        .iter()
+                        .flat_map(|(_, def)| &def.objects)
+                        .find(|obj| obj.id == *id)
+                })
+                .filter_map(|obj| obj.portal.as_ref())
+                .flat_map(|portal| portal_destinations(portal, areas));
+            let exits = room.exits.as_ref().iter().map(|exit| exit.to);
+            for to in exits.chain(portals) {
+                if seen.insert(to) {
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+
+    let mut issues = vec![];
+    for (area, area_def) in areas {
+        for room in &area_def.rooms {
+            if !seen.contains(&room.id) {
+                issues.push(LintIssue {
+                    area: area.clone(),
+                    lint: Lint::UnreachableRoom(room.id),
+                });
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lint_areas, Lint};
+    use crate::area::AreaDef;
+    use crate::character::CharId;
+    use crate::object::ObjectId;
+    use crate::room::RoomId;
+
+    fn lints(toml: &str) -> Vec<Lint> {
+        let area: AreaDef = toml::from_str(toml).expect("Test area didn't parse");
+        lint_areas(&[("test".to_string(), area)])
+            .into_iter()
+            .map(|issue| issue.lint)
+            .collect()
+    }
+
+    const HEADER: &str = r#"
+        name = "Test"
+        author = "Test"
+        npcs = []
+        objects = []
+    "#;
+
+    #[test]
+    fn clean_area() {
+        let area = format!(
+            "{}{}",
+            HEADER,
+            r#"
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = [{ dir = "North", to = 2 }]
+
+            [[rooms]]
+            id = 2
+            name = "Two"
+            description = "Two."
+            exits = [{ dir = "South", to = 1 }]
+            "#
+        );
+        assert_eq!(lints(&area), vec![]);
+    }
+
+    #[test]
+    fn dangling_and_unreachable() {
+        let area = format!(
+            "{}{}",
+            HEADER,
+            r#"
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = [{ dir = "North", to = 9 }]
+
+            [[rooms]]
+            id = 2
+            name = "Two"
+            description = "Two."
+            exits = []
+            "#
+        );
+        let found = lints(&area);
+        assert!(found.contains(&Lint::DanglingExit {
+            room: RoomId::from(1),
+            dir: "north".to_string(),
+            to: RoomId::from(9),
+        }));
+        assert!(found.contains(&Lint::UnreachableRoom(RoomId::from(2))));
+    }
+
+    #[test]
+    fn asymmetric_exit() {
+        let area = format!(
+            "{}{}",
+            HEADER,
+            r#"
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = [{ dir = "North", to = 2 }]
+
+            [[rooms]]
+            id = 2
+            name = "Two"
+            description = "Two."
+            exits = [{ dir = "East", to = 1 }]
+            "#
+        );
+        let found = lints(&area);
+        assert_eq!(found.len(), 2);
+        assert!(found
+            .iter()
+            .all(|lint| matches!(lint, Lint::AsymmetricExit { .. })));
+    }
+
+    #[test]
+    fn duplicate_room_and_long_description() {
+        let area = format!(
+            "{}{}{}{}",
+            HEADER,
+            r#"
+            [[rooms]]
+            id = 1
+            name = "One"
+            exits = []
+            description = ""#,
+            "x".repeat(90),
+            r#""
+
+            [[rooms]]
+            id = 1
+            name = "One again"
+            description = "One."
+            exits = []
+            "#
+        );
+        let found = lints(&area);
+        assert!(found
+            .iter()
+            .any(|lint| matches!(lint, Lint::DuplicateRoom(_))));
+        assert!(found
+            .iter()
+            .any(|lint| matches!(lint, Lint::OverlongDescription { width: 90, .. })));
+    }
+
+    #[test]
+    fn shop_stock() {
+        let area = format!(
+            "{}{}",
+            r#"
+            name = "Test"
+            author = "Test"
+
+            [[npcs]]
+            id = 1
+            keywords = ["peddler"]
+            formal-name = "a peddler"
+            pronoun = "They"
+            in-room = 1
+            shop = { stock = [1, 2] }
+
+            [[objects]]
+            id = 1
+            keywords = ["torch"]
+            name = "a torch"
+            room-description = "A torch is here."
+            object-type = "Light"
+            "#,
+            r#"
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = []
+            "#
+        );
+        assert_eq!(
+            lints(&area),
+            vec![Lint::MissingStock {
+                npc: CharId::from(1),
+                object: ObjectId::from(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn no_mob_room() {
+        let area = r#"
+            name = "Test"
+            author = "Test"
+            objects = []
+
+            [[npcs]]
+            id = 1
+            keywords = ["guard"]
+            formal-name = "a guard"
+            pronoun = "They"
+            in-room = 1
+
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = []
+            flags = ["NoMob"]
+        "#;
+        assert_eq!(
+            lints(area),
+            vec![Lint::NpcInNoMobRoom {
+                npc: CharId::from(1),
+                room: RoomId::from(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn portals() {
+        let area = r#"
+            name = "Test"
+            author = "Test"
+            npcs = []
+
+            [[objects]]
+            id = 1
+            keywords = ["portal"]
+            name = "a shimmering portal"
+            room-description = "A shimmering portal hangs in the air."
+            object-type = "Portal"
+            portal = { pair = 2 }
+
+            [[objects]]
+            id = 2
+            keywords = ["portal"]
+            name = "a shimmering portal"
+            room-description = "A shimmering portal hangs in the air."
+            object-type = "Portal"
+            portal = { to = 5 }
+
+            [[rooms]]
+            id = 1
+            name = "One"
+            description = "One."
+            exits = []
+            load-objects = [1]
+
+            [[rooms]]
+            id = 2
+            name = "Two"
+            description = "Two."
+            exits = []
+            load-objects = [2]
+        "#;
+        // Room 2 is only reachable through the portal.
+        assert_eq!(
+            lints(area),
+            vec![
+                Lint::UnpairedPortal {
+                    object: ObjectId::from(1),
+                    pair: ObjectId::from(2),
+                },
+                Lint::DanglingPortal {
+                    object: ObjectId::from(2),
+                    to: RoomId::from(5),
+                },
+            ]
+        );
+    }
+}
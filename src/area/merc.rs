@@ -0,0 +1,950 @@
+// Converts classic Merc 2.2 `.are` files into our own area format. Only the
+// sections that have something to map onto are read; everything else is
+// skipped and mentioned in the import notes.
+
+use super::AreaDef;
+use crate::character::{CharId, CharacterData, Position, Pronoun, Shop};
+use crate::object::{
+    Container, Drink, Food, Furniture, Liquid, Medicine, ObjectDef, ObjectId, ObjectType,
+    WearLocation,
+};
+use crate::room::{
+    Closeable, Direction, Door, Exit, ExtraDescription, Lockable, RoomDef, RoomFlag, RoomId,
+    SectorType,
+};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub struct MercError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for MercError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug)]
+pub struct MercImport {
+    pub area: AreaDef,
+    pub notes: Vec<String>,
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, MercError> {
+        Err(MercError {
+            line: self.input[..self.pos].matches('\n').count() + 1,
+            message: message.to_string(),
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek_letter(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.pos..].chars().next()
+    }
+
+    fn letter(&mut self) -> Result<char, MercError> {
+        match self.peek_letter() {
+            Some(ch) => {
+                self.pos += ch.len_utf8();
+                Ok(ch)
+            }
+            None => self.error("unexpected end of file"),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), MercError> {
+        if self.letter()? == expected {
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", expected))
+        }
+    }
+
+    fn word(&mut self) -> Result<&'a str, MercError> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|ch: char| ch.is_whitespace())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("unexpected end of file");
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    // Merc numbers can be written as a sum of flags, like `1|4|32`.
+    fn number(&mut self) -> Result<i64, MercError> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .char_indices()
+            .find(|(i, ch)| !(ch.is_ascii_digit() || (*i == 0 && (*ch == '-' || *ch == '+'))))
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let n = match rest[..len].parse::<i64>() {
+            Ok(n) => n,
+            Err(_) => return self.error("expected a number"),
+        };
+        self.pos += len;
+        if self.input[self.pos..].starts_with('|') {
+            self.pos += 1;
+            return Ok(n + self.number()?);
+        }
+        Ok(n)
+    }
+
+    fn string(&mut self) -> Result<String, MercError> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        match rest.find('~') {
+            Some(len) => {
+                self.pos += len + 1;
+                Ok(rest[..len].replace('\r', ""))
+            }
+            None => self.error("unterminated string"),
+        }
+    }
+
+    fn skip_to_eol(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.find('\n').map(|i| i + 1).unwrap_or(rest.len());
+    }
+}
+
+struct Reset {
+    command: char,
+    arg1: i64,
+    arg2: i64,
+    arg3: i64,
+}
+
+pub fn import(input: &str, file_name: &str) -> Result<MercImport, MercError> {
+    let mut reader = Reader { input, pos: 0 };
+    let mut area = AreaDef {
+        file_name: file_name.to_string(),
+        ..Default::default()
+    };
+    let mut notes = vec![];
+    let mut resets = vec![];
+
+    loop {
+        reader.expect('#')?;
+        match reader.word()? {
+            "AREA" => read_area(&mut reader, &mut area)?,
+            "HELPS" => {
+                skip_helps(&mut reader)?;
+                notes.push(String::from("#HELPS section skipped"));
+            }
+            "MOBILES" => read_mobiles(&mut reader, &mut area, &mut notes)?,
+            "OBJECTS" => read_objects(&mut reader, &mut area, &mut notes)?,
+            "ROOMS" => read_rooms(&mut reader, &mut area, &mut notes)?,
+            "RESETS" => read_resets(&mut reader, &mut resets)?,
+            "SHOPS" => read_shops(&mut reader, &mut area, &mut notes)?,
+            "SPECIALS" => {
+                while reader.letter()? != 'S' {
+                    reader.skip_to_eol();
+                }
+                notes.push(String::from("#SPECIALS section skipped"));
+            }
+            "$" => break,
+            _ => return reader.error("unknown section"),
+        }
+    }
+
+    apply_resets(&mut area, resets, &mut notes);
+    Ok(MercImport { area, notes })
+}
+
+fn read_area(reader: &mut Reader, area: &mut AreaDef) -> Result<(), MercError> {
+    // Usually looks like `{ 5 35} Hatchet  Mud School`: levels, author, then name.
+    let header = reader.string()?;
+    let header = header.trim();
+    let header = match header
+        .strip_prefix('{')
+        .and_then(|h| h.find('}').map(|i| &h[i + 1..]))
+    {
+        Some(rest) => rest.trim(),
+        None => header,
+    };
+    match header.find(char::is_whitespace) {
+        Some(i) if header.starts_with(char::is_alphabetic) => {
+            area.author = header[..i].to_string();
+            area.name = header[i..].trim().to_string();
+        }
+        _ => area.name = header.to_string(),
+    }
+    Ok(())
+}
+
+fn skip_helps(reader: &mut Reader) -> Result<(), MercError> {
+    loop {
+        reader.number()?;
+        if reader.string()?.trim() == "$" {
+            return Ok(());
+        }
+        reader.string()?;
+    }
+}
+
+fn read_mobiles(
+    reader: &mut Reader,
+    area: &mut AreaDef,
+    notes: &mut Vec<String>,
+) -> Result<(), MercError> {
+    let mut any = false;
+    loop {
+        reader.expect('#')?;
+        let vnum = reader.number()?;
+        if vnum == 0 {
+            break;
+        }
+        any = true;
+        let keywords = reader.string()?;
+        let short_description = reader.string()?;
+        let long_description = reader.string()?;
+        let description = reader.string()?;
+        // act, affected_by, alignment, 'S'
+        reader.number()?;
+        reader.number()?;
+        reader.number()?;
+        reader.expect('S')?;
+        // level, hitroll, armor class, hit dice, damage dice, gold, exp
+        reader.number()?;
+        reader.number()?;
+        reader.number()?;
+        for _ in 0..2 {
+            reader.number()?;
+            reader.expect('d')?;
+            reader.number()?;
+            reader.expect('+')?;
+            reader.number()?;
+        }
+        reader.number()?;
+        reader.number()?;
+        // position, default position, sex
+        let position = match reader.number()? {
+            4 => Position::Sleeping,
+            5 => Position::Resting,
+            _ => Position::Standing,
+        };
+        reader.number()?;
+        let pronoun = match reader.number()? {
+            1 => Pronoun::He,
+            2 => Pronoun::She,
+            _ => Pronoun::It,
+        };
+
+        area.npcs.push(CharacterData {
+            id: CharId::from(vnum as u32),
+            keywords: keywords.split_whitespace().map(str::to_string).collect(),
+            formal_name: short_description.trim().to_string(),
+            room_description: Some(long_description.trim().to_string()),
+            description: Some(description).filter(|d| !d.trim().is_empty()),
+            pronoun,
+            position,
+            ..Default::default()
+        });
+    }
+    if any {
+        notes.push(String::from(
+            "mobile stats (level, dice, gold, exp, flags) skipped",
+        ));
+    }
+    Ok(())
+}
+
+fn object_type(item_type: i64, wear_flags: i64) -> (ObjectType, Option<&'static str>) {
+    const WEAR_FINGER: i64 = 2;
+    const WEAR_NECK: i64 = 4;
+    const WEAR_WRIST: i64 = 4096;
+    match item_type {
+        1 => (ObjectType::Light, None),
+        2 => (ObjectType::Book, Some("scroll")),
+        3 => (ObjectType::Trash, Some("wand")),
+        4 => (ObjectType::Trash, Some("staff")),
+        5 => (ObjectType::Weapon, None),
+        8 => (ObjectType::Treasure, None),
+        9 if wear_flags & (WEAR_FINGER | WEAR_NECK | WEAR_WRIST) != 0 => {
+            (ObjectType::Jewelry, None)
+        }
+        9 => (ObjectType::Armor, None),
+        10 => (ObjectType::Medicine, None),
+        12 => (ObjectType::Furniture, None),
+        13 => (ObjectType::Trash, None),
+        15 => (ObjectType::Container, None),
+        17 => (ObjectType::Drink, None),
+        18 => (ObjectType::Trash, Some("key")),
+        19 => (ObjectType::Food, None),
+        20 => (ObjectType::Treasure, Some("money")),
+        22 => (ObjectType::Trash, Some("boat")),
+        23 | 24 => (ObjectType::Trash, Some("corpse")),
+        25 => (ObjectType::Drink, None),
+        26 => (ObjectType::Medicine, None),
+        _ => (ObjectType::Trash, Some("unknown item type")),
+    }
+}
+
+fn read_objects(
+    reader: &mut Reader,
+    area: &mut AreaDef,
+    notes: &mut Vec<String>,
+) -> Result<(), MercError> {
+    loop {
+        reader.expect('#')?;
+        let vnum = reader.number()?;
+        if vnum == 0 {
+            return Ok(());
+        }
+        let keywords: Vec<String> = reader
+            .string()?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        let short_description = reader.string()?;
+        let long_description = reader.string()?;
+        reader.string()?; // action description
+        let item_type = reader.number()?;
+        reader.number()?; // extra flags
+        let wear_flags = reader.number()?;
+        let mut values = [0; 4];
+        for value in values.iter_mut() {
+            *value = reader.number()?;
+        }
+        let weight = reader.number()?;
+        let cost = reader.number()?;
+        reader.number()?; // cost per day
+
+        let mut description = None;
+        loop {
+            match reader.peek_letter() {
+                Some('A') => {
+                    reader.letter()?;
+                    reader.number()?;
+                    reader.number()?;
+                    notes.push(format!("object {}: stat applies skipped", vnum));
+                }
+                Some('E') => {
+                    reader.letter()?;
+                    let extra_keywords = reader.string()?;
+                    let extra_description = reader.string()?;
+                    // An extra desc on the object's own keywords is what Merc shows when
+                    // the object is looked at.
+                    if description.is_none()
+                        && extra_keywords
+                            .split_whitespace()
+                            .any(|kw| keywords.iter().any(|own| own == kw))
+                    {
+                        description = Some(extra_description);
+                    } else {
+                        notes.push(format!(
+                            "object {}: extra description '{}' skipped",
+                            vnum, extra_keywords
+                        ));
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let (object_type, lost) = object_type(item_type, wear_flags);
+        if object_type == ObjectType::Medicine && values[1..].iter().any(|spell| *spell > 0) {
+            notes.push(format!("object {}: spells skipped", vnum));
+        }
+        if let Some(lost) = lost {
+            notes.push(format!(
+                "object {}: Merc item type {} ({}) imported as {:?}",
+                vnum, item_type, lost, object_type
+            ));
+        }
+        area.objects.push(ObjectDef {
+            id: ObjectId::from(vnum as usize),
+            keywords,
+            name: short_description.trim().to_string(),
+            room_description: long_description.trim().to_string(),
+            description,
+            object_type,
+            wear: match object_type {
+                ObjectType::Light => Some(WearLocation::Light),
+                _ => wear_location(wear_flags),
+            },
+            weight: weight.max(0) as u32,
+            value: cost.max(0) as u32,
+            // Merc lights keep their hours in value2, where 0 or less burns forever.
+            light_hours: match object_type {
+                ObjectType::Light if values[2] > 0 => Some(values[2] as u32),
+                _ => None,
+            },
+            container: match object_type {
+                ObjectType::Container => Some(container(values)),
+                _ => None,
+            },
+            // Food keeps its hours in value0 and a poison flag in value3.
+            food: match object_type {
+                ObjectType::Food => Some(Food {
+                    hours: values[0].max(0) as u32,
+                    poisoned: values[3] != 0,
+                }),
+                _ => None,
+            },
+            drink: match object_type {
+                ObjectType::Drink => Some(drink(values, item_type == 25)),
+                _ => None,
+            },
+            medicine: match object_type {
+                ObjectType::Medicine => Some(Medicine::default()),
+                _ => None,
+            },
+            book: None,
+            portal: None,
+            furniture: match object_type {
+                ObjectType::Furniture => Some(Furniture::default()),
+                _ => None,
+            },
+        });
+    }
+}
+
+// Merc lets an object have several wear bits, but only the first one set counts
+// here. Bit 0 just means it can be picked up.
+fn wear_location(wear_flags: i64) -> Option<WearLocation> {
+    WearLocation::ALL
+        .iter()
+        .filter(|location| **location != WearLocation::Light)
+        .enumerate()
+        .find(|(bit, _)| wear_flags & (2 << bit) != 0)
+        .map(|(_, location)| *location)
+}
+
+// Merc drink containers hold their capacity, amount left, liquid, and a poison
+// flag in values 0 through 3. Fountains only use the liquid.
+fn drink(values: [i64; 4], fountain: bool) -> Drink {
+    let liquid = Liquid::ALL
+        .get(values[2].max(0) as usize)
+        .copied()
+        .unwrap_or_default();
+    Drink {
+        liquid: Cell::new(liquid),
+        capacity: if fountain { 0 } else { values[0].max(0) as u32 },
+        amount: Cell::new(if fountain { 0 } else { values[1].max(0) as u32 }),
+        fountain,
+        poisoned: Cell::new(values[3] != 0),
+    }
+}
+
+// Merc containers hold a weight in value0, lid flags in value1, and a key in value2.
+// We count items instead of weight, so the capacity is a rough guess.
+fn container(values: [i64; 4]) -> Container {
+    const CLOSEABLE: i64 = 1;
+    const CLOSED: i64 = 4;
+    const LOCKED: i64 = 8;
+    let closed = if values[1] & (CLOSED | LOCKED) != 0 {
+        Closeable::Closed
+    } else {
+        Closeable::Open
+    };
+    let key = if values[2] > 0 {
+        Some(ObjectId::from(values[2] as usize))
+    } else {
+        None
+    };
+    let lid = match key {
+        Some(_) if values[1] & LOCKED != 0 => Door::Lockable(closed, Lockable::Locked),
+        Some(_) => Door::Lockable(closed, Lockable::Unlocked),
+        None if values[1] & (CLOSEABLE | CLOSED) != 0 => Door::Closable(closed),
+        None => Door::None,
+    };
+    Container {
+        capacity: (values[0].max(10) / 10) as usize,
+        lid: Cell::new(lid),
+        key,
+    }
+}
+
+fn read_rooms(
+    reader: &mut Reader,
+    area: &mut AreaDef,
+    notes: &mut Vec<String>,
+) -> Result<(), MercError> {
+    loop {
+        reader.expect('#')?;
+        let vnum = reader.number()?;
+        if vnum == 0 {
+            return Ok(());
+        }
+        let name = reader.string()?;
+        let description = reader.string()?;
+        reader.number()?; // area number
+        let (flags, lost_flags) = room_flags(reader.number()?);
+        if lost_flags != 0 {
+            notes.push(format!("room {}: flags {} skipped", vnum, lost_flags));
+        }
+        let sector = match sector_type(reader.number()?) {
+            Some(sector) => sector,
+            None => {
+                notes.push(format!("room {}: unknown sector, using inside", vnum));
+                SectorType::Inside
+            }
+        };
+
+        let mut exits = vec![];
+        let mut extra_descs = vec![];
+        loop {
+            match reader.letter()? {
+                'S' => break,
+                'D' => {
+                    let dir = match reader.number()? {
+                        0 => Direction::North,
+                        1 => Direction::East,
+                        2 => Direction::South,
+                        3 => Direction::West,
+                        4 => Direction::Up,
+                        5 => Direction::Down,
+                        _ => return reader.error("bad door number"),
+                    };
+                    let exit_description = reader.string()?;
+                    let exit_keywords = reader.string()?;
+                    let locks = reader.number()?;
+                    let key = reader.number()?;
+                    let to = reader.number()?;
+                    let key = if key > 0 {
+                        Some(ObjectId::from(key as usize))
+                    } else {
+                        None
+                    };
+                    let door = match locks {
+                        0 => Door::None,
+                        _ if key.is_some() => Door::Lockable(Closeable::Open, Lockable::Unlocked),
+                        _ => Door::Closable(Closeable::Open),
+                    };
+                    if to >= 0 {
+                        let mut exit = Exit::new(RoomId::from(to as u32), dir);
+                        exit.door = door;
+                        exit.key = key;
+                        exit.keywords = exit_keywords
+                            .split_whitespace()
+                            .map(str::to_lowercase)
+                            .collect();
+                        if !exit_description.trim().is_empty() {
+                            exit.description = Some(exit_description);
+                        }
+                        exits.push(exit);
+                    }
+                }
+                'E' => {
+                    let keywords = reader.string()?;
+                    let description = reader.string()?;
+                    extra_descs.push(ExtraDescription {
+                        keywords: keywords.split_whitespace().map(str::to_lowercase).collect(),
+                        description,
+                    });
+                }
+                _ => return reader.error("unexpected room field"),
+            }
+        }
+
+        area.rooms.push(RoomDef {
+            id: RoomId::from(vnum as u32),
+            name: name.trim().to_string(),
+            description,
+            night_description: None,
+            exits: crate::room::Exits(exits),
+            load_objects: vec![],
+            flags,
+            sector,
+            extra_descs,
+        });
+    }
+}
+
+// Returns the flags we understand, and the bits of the ones we don't.
+fn room_flags(bits: i64) -> (Vec<RoomFlag>, i64) {
+    const FLAGS: &[(i64, RoomFlag)] = &[
+        (1, RoomFlag::Dark),
+        (4, RoomFlag::NoMob),
+        (8, RoomFlag::Indoors),
+        (512, RoomFlag::Private),
+        (1024, RoomFlag::Safe),
+        (8192, RoomFlag::NoRecall),
+    ];
+    let mut flags = vec![];
+    let mut lost = bits;
+    for (bit, flag) in FLAGS {
+        if bits & bit != 0 {
+            flags.push(*flag);
+            lost &= !bit;
+        }
+    }
+    (flags, lost)
+}
+
+fn sector_type(sector: i64) -> Option<SectorType> {
+    match sector {
+        0 => Some(SectorType::Inside),
+        1 => Some(SectorType::City),
+        2 => Some(SectorType::Field),
+        3 => Some(SectorType::Forest),
+        4 => Some(SectorType::Hills),
+        5 => Some(SectorType::Mountain),
+        6 => Some(SectorType::ShallowWater),
+        7 => Some(SectorType::DeepWater),
+        9 => Some(SectorType::Air),
+        10 => Some(SectorType::Desert),
+        _ => None,
+    }
+}
+
+fn read_shops(
+    reader: &mut Reader,
+    area: &mut AreaDef,
+    notes: &mut Vec<String>,
+) -> Result<(), MercError> {
+    loop {
+        let keeper = reader.number()?;
+        if keeper == 0 {
+            return Ok(());
+        }
+        let mut buys = vec![];
+        for _ in 0..5 {
+            let item_type = reader.number()?;
+            let (object_type, _) = object_type(item_type, 0);
+            if item_type != 0 && !buys.contains(&object_type) {
+                buys.push(object_type);
+            }
+        }
+        let sells_at = reader.number()?;
+        let buys_at = reader.number()?;
+        let opens = reader.number()?;
+        let closes = reader.number()?;
+        reader.skip_to_eol();
+        let id = CharId::from(keeper as u32);
+        match area.npcs.iter_mut().find(|npc| npc.id == id) {
+            Some(npc) => {
+                npc.shop = Some(Shop {
+                    stock: vec![],
+                    buys,
+                    sells_at: sells_at.max(0) as u32,
+                    buys_at: buys_at.max(0) as u32,
+                    opens: opens.clamp(0, 24) as u64,
+                    closes: closes.clamp(0, 24) as u64,
+                })
+            }
+            None => notes.push(format!("shop for mobile {} from another area skipped", id)),
+        }
+    }
+}
+
+fn read_resets(reader: &mut Reader, resets: &mut Vec<Reset>) -> Result<(), MercError> {
+    loop {
+        let command = reader.letter()?;
+        match command {
+            'S' => return Ok(()),
+            '*' => reader.skip_to_eol(),
+            _ => {
+                reader.number()?; // if-flag
+                let arg1 = reader.number()?;
+                let arg2 = reader.number()?;
+                let arg3 = if command == 'G' || command == 'R' {
+                    0
+                } else {
+                    reader.number()?
+                };
+                reader.skip_to_eol();
+                resets.push(Reset {
+                    command,
+                    arg1,
+                    arg2,
+                    arg3,
+                });
+            }
+        }
+    }
+}
+
+fn apply_resets(area: &mut AreaDef, resets: Vec<Reset>, notes: &mut Vec<String>) {
+    let mut placed = vec![];
+    // Objects given to a shopkeeper become its stock, so keep track of who was
+    // reset last.
+    let mut last_npc = None;
+    for reset in resets {
+        match reset.command {
+            'M' => {
+                let id = CharId::from(reset.arg1 as u32);
+                let room_id = RoomId::from(reset.arg3 as u32);
+                let in_area = area.rooms.iter().any(|room| room.id == room_id);
+                match area.npcs.iter_mut().find(|npc| npc.id == id) {
+                    Some(_) if !in_area => {
+                        notes.push(format!(
+                            "reset of mobile {} into room {} from another area skipped",
+                            id, room_id
+                        ));
+                        last_npc = None;
+                    }
+                    Some(_) if placed.contains(&id) => notes.push(format!(
+                        "mobile {}: extra reset into room {} skipped",
+                        id, reset.arg3
+                    )),
+                    Some(npc) => {
+                        npc.in_room = room_id;
+                        placed.push(id);
+                        last_npc = Some(id);
+                    }
+                    None => notes.push(format!("reset of mobile {} from another area skipped", id)),
+                }
+            }
+            'O' => {
+                let room_id = RoomId::from(reset.arg3 as u32);
+                let id = ObjectId::from(reset.arg1 as usize);
+                if !area.objects.iter().any(|obj| obj.id == id) {
+                    notes.push(format!("reset of object {} from another area skipped", id));
+                    continue;
+                }
+                match area.rooms.iter_mut().find(|room| room.id == room_id) {
+                    Some(room) => room.load_objects.push(id),
+                    None => notes.push(format!(
+                        "reset of object {} into room {} from another area skipped",
+                        reset.arg1, room_id
+                    )),
+                }
+            }
+            'D' => {
+                let room_id = RoomId::from(reset.arg1 as u32);
+                let dir = match reset.arg2 {
+                    0 => "north",
+                    1 => "east",
+                    2 => "south",
+                    3 => "west",
+                    4 => "up",
+                    _ => "down",
+                };
+                let exit = area
+                    .rooms
+                    .iter_mut()
+                    .find(|room| room.id == room_id)
+                    .and_then(|room| room.exits.get_mut(dir));
+                match exit {
+                    Some(exit) => {
+                        exit.door = match (reset.arg3, exit.door) {
+                            (0, door) => door,
+                            (1, Door::Lockable(..)) => {
+                                Door::Lockable(Closeable::Closed, Lockable::Unlocked)
+                            }
+                            (2, _) => Door::Lockable(Closeable::Closed, Lockable::Locked),
+                            _ => Door::Closable(Closeable::Closed),
+                        }
+                    }
+                    None => notes.push(format!(
+                        "door reset for room {} exit {} skipped",
+                        room_id, dir
+                    )),
+                }
+            }
+            'G' => {
+                let id = ObjectId::from(reset.arg1 as usize);
+                if !area.objects.iter().any(|obj| obj.id == id) {
+                    notes.push(format!(
+                        "object {} from another area given to a mobile skipped",
+                        id
+                    ));
+                    continue;
+                }
+                let shop = area
+                    .npcs
+                    .iter_mut()
+                    .filter(|npc| Some(npc.id) == last_npc)
+                    .find_map(|npc| npc.shop.as_mut());
+                match shop {
+                    Some(shop) => shop.stock.push(id),
+                    None => notes.push(format!("object {} given to a mobile skipped", reset.arg1)),
+                }
+            }
+            'E' => notes.push(format!(
+                "object {} equipped on a mobile skipped",
+                reset.arg1
+            )),
+            'P' => notes.push(format!(
+                "object {} put into object {} skipped",
+                reset.arg1, reset.arg3
+            )),
+            'R' => notes.push(format!("exit randomization in room {} skipped", reset.arg1)),
+            other => notes.push(format!("unknown reset command '{}' skipped", other)),
+        }
+    }
+    // Without a room in this area there's nowhere to put them.
+    area.npcs.retain(|npc| {
+        if !placed.contains(&npc.id) {
+            notes.push(format!(
+                "mobile {} is never reset into this area; dropped",
+                npc.id
+            ));
+        }
+        placed.contains(&npc.id)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::import;
+    use crate::object::{ObjectId, ObjectType, WearLocation};
+    use crate::room::{RoomFlag, RoomId, SectorType};
+
+    const SAMPLE: &str = "#AREA	{ 1 5} Hatchet Mud School~
+
+#MOBILES
+#3000
+wizard~
+the wizard~
+A wizard walks around behind the counter, talking to himself.
+~
+The wizard looks old and senile.
+~
+1|2|64 0 900 S
+33 0 0 0d0+0 0d0+0
+0 0
+8 8 1
+#0
+
+#OBJECTS
+#3001
+sword long~
+a long sword~
+A long sword has been left here.~
+~
+5 0 8193
+0 1 8 3
+10 100 10
+E
+sword~
+It's long, and it's a sword.
+~
+#0
+
+#ROOMS
+#3001
+The Temple~
+You are in the temple.
+~
+0 2056 3
+D0
+~
+door~
+2 3001 3002
+S
+#3002
+The Altar~
+You are by the altar.
+~
+0 4 0
+D2
+~
+door~
+1 0 3001
+E
+altar stone~
+The altar is carved from a single block of granite.
+~
+S
+#0
+
+#RESETS
+M 0 3000 1 3002
+G 0 3001 1
+O 0 3001 1 3001
+D 0 3001 0 2
+S
+
+#SHOPS
+3000 5 0 0 0 0 120 80 6 22 ; the wizard
+0
+
+#$
+";
+
+    #[test]
+    fn imports_sample() {
+        let import = import(SAMPLE, "school").expect("Sample didn't import");
+        let area = import.area;
+        assert_eq!(area.name, "Mud School");
+        assert_eq!(area.author, "Hatchet");
+        assert_eq!(area.npcs.len(), 1);
+        assert_eq!(area.npcs[0].in_room, RoomId::from(3002));
+        let shop = area.npcs[0]
+            .shop
+            .as_ref()
+            .expect("Wizard should keep a shop");
+        assert_eq!(shop.stock, vec![ObjectId::from(3001)]);
+        assert_eq!(shop.buys, vec![ObjectType::Weapon]);
+        assert_eq!((shop.opens, shop.closes), (6, 22));
+        assert_eq!(area.objects[0].object_type, ObjectType::Weapon);
+        assert_eq!(area.objects[0].wear, Some(WearLocation::Wield));
+        assert_eq!(area.objects[0].weight, 10);
+        assert_eq!(area.objects[0].value, 100);
+        assert_eq!(
+            area.objects[0].description.as_deref(),
+            Some("It's long, and it's a sword.\n")
+        );
+        assert_eq!(area.rooms.len(), 2);
+        assert_eq!(area.rooms[0].load_objects.len(), 1);
+        assert!(area.rooms[0].exits.get("north").unwrap().door.is_closed());
+        assert_eq!(area.rooms[0].flags, vec![RoomFlag::Indoors]);
+        assert_eq!(area.rooms[0].sector, SectorType::Forest);
+        assert_eq!(area.rooms[1].flags, vec![RoomFlag::NoMob]);
+        assert_eq!(
+            area.rooms[1].extra_descs[0].keywords,
+            vec!["altar", "stone"]
+        );
+        assert!(import.notes.iter().any(|note| note.contains("flags 2048")));
+    }
+
+    #[test]
+    fn skips_resets_from_other_areas() {
+        let sample = SAMPLE
+            .replace("M 0 3000 1 3002", "M 0 3000 1 9001")
+            .replace("O 0 3001 1 3001", "O 0 9001 1 3001");
+        let import = import(&sample, "school").expect("Sample didn't import");
+        let area = import.area;
+        assert!(area.npcs.is_empty());
+        assert!(area.rooms[0].load_objects.is_empty());
+        assert!(import
+            .notes
+            .iter()
+            .any(|note| note.contains("mobile 3000 into room 9001 from another area")));
+        assert!(import
+            .notes
+            .iter()
+            .any(|note| note.contains("object 9001 from another area")));
+        assert!(import
+            .notes
+            .iter()
+            .any(|note| note.contains("mobile 3000 is never reset into this area; dropped")));
+    }
+
+    #[test]
+    fn reports_bad_input() {
+        let err = import("#AREA nothing~\n#BOGUS\n", "bogus").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn output_round_trips() {
+        let import = import(SAMPLE, "school").unwrap();
+        let toml = import.area.to_toml().unwrap();
+        let parsed: crate::area::AreaDef = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.rooms.len(), 2);
+        assert!(parsed.rooms[0].exits.get("north").unwrap().door.is_closed());
+    }
+}
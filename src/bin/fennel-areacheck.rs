@@ -0,0 +1,47 @@
+use fennel::lint::lint_areas;
+use fennel::Area;
+use std::process;
+
+// Loads every area (or just the ones named on the command line) the same way
+// the server does at boot, and reports everything that looks wrong with them.
+// Exits non-zero if anything was found, so builder PRs can be checked in CI.
+fn main() {
+    let names: Vec<String> = std::env::args().skip(1).collect();
+    let names = if names.is_empty() {
+        match Area::list() {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("Couldn't list areas: {}", e);
+                process::exit(2);
+            }
+        }
+    } else {
+        names
+    };
+
+    let mut failed = false;
+    let mut areas = Vec::with_capacity(names.len());
+    for name in names {
+        match Area::load(&name) {
+            Ok(area_def) => areas.push((name, area_def)),
+            Err(e) => {
+                println!("{}: couldn't be loaded: {:?}", name, e);
+                failed = true;
+            }
+        }
+    }
+
+    let issues = lint_areas(&areas);
+    for issue in &issues {
+        println!("{}", issue);
+    }
+
+    println!(
+        "Checked {} area(s): {} problem(s) found",
+        areas.len(),
+        issues.len()
+    );
+    if failed || !issues.is_empty() {
+        process::exit(1);
+    }
+}
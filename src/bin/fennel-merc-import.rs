@@ -0,0 +1,46 @@
+use fennel::merc;
+use std::path::Path;
+use std::process;
+
+// Converts a Merc 2.2 `.are` file into a fennel area, written to stdout.
+// Anything that couldn't be translated is reported on stderr.
+//
+//     fennel-merc-import midgaard.are > areas/midgaard.toml
+fn main() {
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: fennel-merc-import <file.are>");
+            process::exit(2);
+        }
+    };
+    let input = match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Couldn't read {}: {}", path, e);
+            process::exit(2);
+        }
+    };
+    let file_name = Path::new(&path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("imported");
+
+    let import = match merc::import(&input, file_name) {
+        Ok(import) => import,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }
+    };
+    for note in &import.notes {
+        eprintln!("{}: {}", path, note);
+    }
+    match import.area.to_toml() {
+        Ok(toml) => print!("{}", toml),
+        Err(e) => {
+            eprintln!("{}: couldn't write TOML: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
@@ -1,40 +1,86 @@
 mod character_data;
+mod condition;
+mod duel;
+mod effect;
+mod equipment;
+mod experience;
 mod player;
 mod player_record;
+mod position;
+mod preferences;
 mod pronoun;
+mod shop;
+mod vitals;
 
 use generational_arena::Index;
 use intrusive_collections::LinkedList;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 
-use crate::object::ObjectOnCharAdapter;
+use crate::object::{Object, ObjectId, ObjectOnCharAdapter};
 use crate::room::RoomId;
+use crate::skill;
+use crate::util::{self, HasKeywords};
 pub use character_data::CharacterData;
+pub use condition::Condition;
+pub use duel::DuelRecord;
+pub use effect::{Effect, EffectKind, Effects, Stat};
+pub use equipment::Equipment;
+pub use experience::{kill_exp, Experience, EXPLORE_EXP, MAX_LEVEL};
 pub use player::Player;
 pub use player_record::PlayerRecord;
+pub use position::Position;
+pub use preferences::Preferences;
 pub use pronoun::Pronoun;
+pub use shop::Shop;
 use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+pub use vitals::{Attributes, Vitals, DEFAULT_PROMPT};
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Hash, Eq, PartialEq, Serialize)]
 pub struct CharId(u32);
 
+impl From<u32> for CharId {
+    fn from(id: u32) -> CharId {
+        CharId(id)
+    }
+}
+
 impl Display for CharId {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
     }
 }
 
+// How much anyone can carry, until there are stats to base it on.
+const MAX_CARRY_COUNT: usize = 30;
+const MAX_CARRY_WEIGHT: u32 = 200;
+
 #[derive(Debug, Default)]
 pub struct Character {
     index: Option<Index>,
     connection: Option<Index>,
     data: CharacterData,
     pub inventory: LinkedList<ObjectOnCharAdapter>,
+    pub equipment: Equipment,
+    // What they're sitting, resting, or sleeping on, if anything
+    furniture: Option<Rc<Object>>,
+    // Who they're attacking
+    fighting: Option<Index>,
+    // Who they've challenged to a duel and are waiting on
+    challenged: Option<Index>,
+    // Who they're duelling, which is a fight nobody dies in
+    duelling: Option<Index>,
+    // Pulses until they can act again after using a skill
+    lag: u32,
 }
 
 impl Character {
-    pub fn from_data(char_data: CharacterData) -> Self {
+    pub fn from_data(mut char_data: CharacterData) -> Self {
+        // Nobody starts out in a fight, whatever they were doing when they were saved.
+        if char_data.position == Position::Fighting {
+            char_data.position = Position::Standing;
+        }
         Character {
             data: char_data,
             ..Default::default()
@@ -60,6 +106,11 @@ impl Character {
         self.data.id
     }
 
+    /// Players all share the default id; npcs each have their own.
+    pub fn is_player(&self) -> bool {
+        self.data.id == CharId::default()
+    }
+
     pub fn keywords(&self) -> &[String] {
         &self.data.keywords
     }
@@ -84,7 +135,256 @@ impl Character {
                 .get(0)
                 .expect("Missing first keyword for name"),
             formal_name: &self.data.formal_name,
+            position: self.data.position,
+            furniture: self.furniture.as_ref().map(|obj| obj.name()),
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.data.position
+    }
+
+    /// Changes position. Getting up leaves any furniture behind.
+    pub fn set_position(&mut self, position: Position) {
+        self.data.position = position;
+        if position >= Position::Fighting {
+            self.furniture = None;
+        }
+    }
+
+    pub fn furniture(&self) -> Option<&Rc<Object>> {
+        self.furniture.as_ref()
+    }
+
+    pub fn set_furniture(&mut self, furniture: Option<Rc<Object>>) {
+        self.furniture = furniture;
+    }
+
+    pub fn fighting(&self) -> Option<Index> {
+        self.fighting
+    }
+
+    /// Starts or stops attacking someone. Anyone who stops is left standing.
+    pub fn set_fighting(&mut self, victim: Option<Index>) {
+        self.fighting = victim;
+        if victim.is_some() {
+            self.set_position(Position::Fighting);
+        } else if self.data.position == Position::Fighting {
+            self.set_position(Position::Standing);
+        }
+    }
+
+    pub fn challenged(&self) -> Option<Index> {
+        self.challenged
+    }
+
+    pub fn set_challenged(&mut self, rival: Option<Index>) {
+        self.challenged = rival;
+    }
+
+    pub fn duelling(&self) -> Option<Index> {
+        self.duelling
+    }
+
+    pub fn set_duelling(&mut self, rival: Option<Index>) {
+        self.duelling = rival;
+    }
+
+    /// Whether the character has an object on them, carried or equipped.
+    pub fn has_object(&self, id: ObjectId) -> bool {
+        self.inventory.iter().any(|obj| obj.id() == id) || self.equipment.contains(id)
+    }
+
+    pub fn gold(&self) -> u32 {
+        self.data.gold
+    }
+
+    pub fn gain_gold(&mut self, amount: u32) {
+        self.data.gold = self.data.gold.saturating_add(amount);
+    }
+
+    /// Takes gold away if the character has enough of it.
+    pub fn spend_gold(&mut self, amount: u32) -> bool {
+        match self.data.gold.checked_sub(amount) {
+            Some(left) => {
+                self.data.gold = left;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn condition(&self) -> &Condition {
+        &self.data.condition
+    }
+
+    pub fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.data.condition
+    }
+
+    pub fn vitals(&self) -> &Vitals {
+        &self.data.vitals
+    }
+
+    pub fn vitals_mut(&mut self) -> &mut Vitals {
+        &mut self.data.vitals
+    }
+
+    /// The character's attributes as they stand, with whatever effects they're under.
+    pub fn attributes(&self) -> Attributes {
+        self.data.attributes.modified(&self.data.effects)
+    }
+
+    /// How much more likely the character is to land a blow, in percent.
+    pub fn hit_bonus(&self) -> i32 {
+        self.data.effects.modifier(Stat::Hit)
+    }
+
+    /// Recovers an hour's worth of hit points, mana, and movement.
+    pub fn regenerate(&mut self) {
+        let condition = self.data.condition;
+        let slowed =
+            condition.full == 0 || condition.quenched == 0 || self.has_effect(EffectKind::Poison);
+        let (position, attributes) = (self.data.position, self.attributes());
+        self.data.vitals.regenerate(position, &attributes, slowed);
+    }
+
+    pub fn experience(&self) -> &Experience {
+        &self.data.experience
+    }
+
+    pub fn level(&self) -> u32 {
+        self.data.experience.level
+    }
+
+    /// Adds experience, and everything that comes with any levels it's worth.
+    /// Returns how many levels that was.
+    pub fn gain_exp(&mut self, amount: u32) -> u32 {
+        let levels = self.data.experience.gain(amount);
+        for _ in 0..levels {
+            let gains = experience::level_gains(&self.attributes());
+            let vitals = &mut self.data.vitals;
+            vitals.max_hp += gains.hp;
+            vitals.max_mana += gains.mana;
+            vitals.max_moves += gains.moves;
+            self.data.practices += gains.practices;
+        }
+        levels
+    }
+
+    /// Fills in a player's prompt format with how they're doing.
+    pub fn prompt(&self, format: &str) -> String {
+        vitals::render_prompt(format, &self.data.vitals, self.data.gold)
+    }
+
+    /// How well the character knows a skill, as a percent.
+    pub fn proficiency(&self, skill: &str) -> u32 {
+        self.data.skills.get(skill).copied().unwrap_or(0)
+    }
+
+    pub fn set_proficiency(&mut self, skill: &str, percent: u32) {
+        self.data.skills.insert(skill.to_string(), percent.min(100));
+    }
+
+    /// Practice makes perfect. Returns whether using the skill just now taught
+    /// the character something.
+    pub fn improve_skill(&mut self, skill: &str, succeeded: bool) -> bool {
+        let learned = self.proficiency(skill);
+        if learned == 0 || learned >= 100 {
+            return false;
+        }
+        if util::random_percent() > skill::improve_chance(learned, succeeded) {
+            return false;
+        }
+        self.set_proficiency(skill, learned + 1);
+        true
+    }
+
+    pub fn practices(&self) -> u32 {
+        self.data.practices
+    }
+
+    /// Uses up a practice session, if there are any left.
+    pub fn spend_practice(&mut self) -> bool {
+        match self.data.practices.checked_sub(1) {
+            Some(left) => {
+                self.data.practices = left;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn lag(&self) -> u32 {
+        self.lag
+    }
+
+    /// Keeps the character from acting for a few pulses.
+    pub fn add_lag(&mut self, pulses: u32) {
+        self.lag = self.lag.max(pulses);
+    }
+
+    pub fn decrement_lag(&mut self) {
+        self.lag = self.lag.saturating_sub(1);
+    }
+
+    pub fn has_effect(&self, kind: EffectKind) -> bool {
+        self.data.effects.has(kind)
+    }
+
+    pub fn add_effect(&mut self, effect: Effect) {
+        self.data.effects.add(effect)
+    }
+
+    /// Ends an effect early. Returns whether there was one to end.
+    pub fn remove_effect(&mut self, kind: EffectKind) -> bool {
+        self.data.effects.remove(kind)
+    }
+
+    /// Counts down an hour on every effect, returning the ones that wore off.
+    pub fn tick_effects(&mut self) -> Vec<EffectKind> {
+        self.data.effects.tick()
+    }
+
+    pub fn shop(&self) -> Option<&Shop> {
+        self.data.shop.as_ref()
+    }
+
+    /// How many things the character has on them, counting what's worn.
+    pub fn carried_count(&self) -> usize {
+        self.inventory.iter().count() + self.equipment.iter().count()
+    }
+
+    pub fn carried_weight(&self) -> u32 {
+        let inventory: u32 = self.inventory.iter().map(|obj| obj.weight()).sum();
+        let equipment: u32 = self.equipment.iter().map(|(_, obj)| obj.weight()).sum();
+        inventory + equipment
+    }
+
+    /// Like `can_carry`, but only counting items, for things whose weight the
+    /// character is already carrying, like what's in their own bag.
+    pub fn has_hands_for(&self, obj: &Object, along_with: &[Rc<Object>]) -> Result<(), String> {
+        if self.carried_count() + along_with.len() >= MAX_CARRY_COUNT {
+            return Err(format!(
+                "{}: you can't carry that many items.",
+                util::capitalize(obj.name())
+            ));
         }
+        Ok(())
+    }
+
+    /// Whether the character could pick up `obj` on top of what they already have
+    /// and whatever else they're picking up along with it.
+    pub fn can_carry(&self, obj: &Object, along_with: &[Rc<Object>]) -> Result<(), String> {
+        self.has_hands_for(obj, along_with)?;
+        let weight = self.carried_weight() + along_with.iter().map(|obj| obj.weight()).sum::<u32>();
+        if weight + obj.weight() > MAX_CARRY_WEIGHT {
+            return Err(format!(
+                "{}: you can't carry that much weight.",
+                util::capitalize(obj.name())
+            ));
+        }
+        Ok(())
     }
 
     pub fn pronoun(&self) -> Pronoun {
@@ -100,6 +400,12 @@ impl Character {
     }
 }
 
+impl HasKeywords for Character {
+    fn keywords(&self) -> &[String] {
+        &self.data.keywords
+    }
+}
+
 #[derive(Debug)]
 pub struct Description<'ch> {
     description: Option<&'ch str>,
@@ -125,15 +431,30 @@ pub struct RoomDescription<'ch> {
     room_description: Option<&'ch str>,
     name: &'ch str,
     formal_name: &'ch str,
-    // position: Position,
+    position: Position,
+    furniture: Option<&'ch str>,
 }
 
 impl<'ch> Display for RoomDescription<'ch> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some(desc) = self.room_description {
-            desc.fmt(f)
-        } else {
-            write!(f, "{} {{ {} }} is here.", self.formal_name, self.name)
+        match (self.position, self.furniture, self.room_description) {
+            (position, Some(furniture), _) => write!(
+                f,
+                "{} is {} on {}.",
+                util::capitalize(self.formal_name),
+                position.verb(),
+                furniture
+            ),
+            (Position::Standing, None, Some(desc)) => desc.fmt(f),
+            (Position::Standing, None, None) => {
+                write!(f, "{} {{ {} }} is here.", self.formal_name, self.name)
+            }
+            (position, None, _) => write!(
+                f,
+                "{} is {} here.",
+                util::capitalize(self.formal_name),
+                position.verb()
+            ),
         }
     }
 }
@@ -1,6 +1,9 @@
-use crate::character::{CharId, Pronoun};
+use crate::character::{
+    Attributes, CharId, Condition, Effects, Experience, Position, Pronoun, Shop, Vitals,
+};
 use crate::room::RoomId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::default::Default;
 
 // #[derive(Copy, Clone, Debug, Default, Deserialize, Hash, Eq, PartialEq, Serialize)]
@@ -9,16 +12,45 @@ use std::default::Default;
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct CharacterData {
-    #[serde(default, skip_serializing)]
-    pub(super) id: CharId,
-    pub(super) keywords: Vec<String>,
-    pub(super) formal_name: String,
-    #[serde(skip_serializing)]
-    pub(super) room_description: Option<String>,
-    pub(super) description: Option<String>,
-    pub(super) pronoun: Pronoun,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) id: CharId,
+    pub(crate) keywords: Vec<String>,
+    pub(crate) formal_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) room_description: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) pronoun: Pronoun,
     #[serde(default)]
-    pub(super) in_room: RoomId,
+    pub(crate) in_room: RoomId,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) position: Position,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) vitals: Vitals,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) attributes: Attributes,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) experience: Experience,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) gold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) shop: Option<Shop>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) condition: Condition,
+    #[serde(default, skip_serializing_if = "Effects::is_empty")]
+    pub(crate) effects: Effects,
+    // How well they know each skill they've learned, as a percent
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) skills: BTreeMap<String, u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub(crate) practices: u32,
+}
+
+// Enough to get a new player started on a few skills.
+const STARTING_PRACTICES: u32 = 10;
+
+// Players all share the default id, and don't need it written to their pfiles.
+fn is_default<T: Default + PartialEq>(t: &T) -> bool {
+    *t == T::default()
 }
 
 impl CharacterData {
@@ -27,6 +59,7 @@ impl CharacterData {
             keywords,
             formal_name,
             pronoun,
+            practices: STARTING_PRACTICES,
             ..Default::default()
         }
     }
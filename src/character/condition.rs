@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+// Nobody can be fuller than this, and it's how many game hours a full belly lasts.
+pub const MAX_CONDITION: u32 = 48;
+// Past this, there's no room for more.
+const STUFFED: u32 = 40;
+
+// How well fed and watered a character is. Both wear down by one every game hour.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Condition {
+    pub full: u32,
+    pub quenched: u32,
+}
+
+impl Default for Condition {
+    fn default() -> Condition {
+        Condition {
+            full: MAX_CONDITION,
+            quenched: MAX_CONDITION,
+        }
+    }
+}
+
+impl Condition {
+    pub fn is_stuffed(&self) -> bool {
+        self.full > STUFFED
+    }
+
+    pub fn is_sloshing(&self) -> bool {
+        self.quenched > STUFFED
+    }
+
+    /// Changes how full and quenched a character is. Some drinks leave you thirstier.
+    pub fn nourish(&mut self, full: i32, quenched: i32) {
+        let adjust =
+            |value: u32, by: i32| (value as i32 + by).clamp(0, MAX_CONDITION as i32) as u32;
+        self.full = adjust(self.full, full);
+        self.quenched = adjust(self.quenched, quenched);
+    }
+
+    /// Wears down by an hour, returning complaints about anything that's run out.
+    pub fn tick(&mut self) -> Vec<&'static str> {
+        self.full = self.full.saturating_sub(1);
+        self.quenched = self.quenched.saturating_sub(1);
+        let mut complaints = vec![];
+        if self.full == 0 {
+            complaints.push("You are hungry.");
+        }
+        if self.quenched == 0 {
+            complaints.push("You are thirsty.");
+        }
+        complaints
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Condition, MAX_CONDITION};
+
+    #[test]
+    fn wears_down_and_complains() {
+        let mut condition = Condition {
+            full: 1,
+            quenched: 2,
+        };
+        assert_eq!(condition.tick(), vec!["You are hungry."]);
+        assert_eq!(
+            condition.tick(),
+            vec!["You are hungry.", "You are thirsty."]
+        );
+        assert_eq!(condition.full, 0);
+    }
+
+    #[test]
+    fn nourishment_is_clamped() {
+        let mut condition = Condition {
+            full: 45,
+            quenched: 1,
+        };
+        condition.nourish(10, -5);
+        assert_eq!(condition.full, MAX_CONDITION);
+        assert_eq!(condition.quenched, 0);
+        assert!(condition.is_stuffed());
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Tally {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+// How a player has done in duels against each rival, by the rival's name.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct DuelRecord(BTreeMap<String, Tally>);
+
+impl DuelRecord {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn record_win(&mut self, rival: &str) {
+        self.0.entry(rival.to_string()).or_default().wins += 1;
+    }
+
+    pub fn record_loss(&mut self, rival: &str) {
+        self.0.entry(rival.to_string()).or_default().losses += 1;
+    }
+
+    pub fn against(&self, rival: &str) -> Tally {
+        self.0.get(rival).copied().unwrap_or_default()
+    }
+
+    /// Every duel, against anyone.
+    pub fn total(&self) -> Tally {
+        self.0
+            .values()
+            .fold(Tally::default(), |total, tally| Tally {
+                wins: total.wins + tally.wins,
+                losses: total.losses + tally.losses,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DuelRecord, Tally};
+
+    #[test]
+    fn tallies_each_rival() {
+        let mut record = DuelRecord::default();
+        record.record_win("Rhane");
+        record.record_win("Rhane");
+        record.record_loss("Rhane");
+        record.record_loss("Tamsin");
+        assert_eq!(record.against("Rhane"), Tally { wins: 2, losses: 1 });
+        assert_eq!(record.against("Nobody"), Tally::default());
+        assert_eq!(record.total(), Tally { wins: 2, losses: 2 });
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EffectKind {
+    Poison,
+    Infravision,
+    Bless,
+    GiantStrength,
+    Invisible,
+    DetectInvisible,
+    // Only objects glow
+    Glow,
+}
+
+impl EffectKind {
+    pub const ALL: &'static [EffectKind] = &[
+        EffectKind::Poison,
+        EffectKind::Infravision,
+        EffectKind::Bless,
+        EffectKind::GiantStrength,
+        EffectKind::Invisible,
+        EffectKind::DetectInvisible,
+        EffectKind::Glow,
+    ];
+
+    pub fn parse(name: &str) -> Option<EffectKind> {
+        EffectKind::ALL
+            .iter()
+            .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub fn start_message(&self) -> &'static str {
+        match self {
+            EffectKind::Poison => "You feel very sick.",
+            EffectKind::Infravision => "Your eyes glow red.",
+            EffectKind::Bless => "You feel righteous.",
+            EffectKind::GiantStrength => "Your muscles surge with heightened power!",
+            EffectKind::Invisible => "You fade out of existence.",
+            EffectKind::DetectInvisible => "Your eyes tingle.",
+            EffectKind::Glow => "You glow.",
+        }
+    }
+
+    pub fn end_message(&self) -> &'static str {
+        match self {
+            EffectKind::Poison => "You feel better.",
+            EffectKind::Infravision => "You no longer see in the dark.",
+            EffectKind::Bless => "You feel less righteous.",
+            EffectKind::GiantStrength => "You feel weaker.",
+            EffectKind::Invisible => "You are no longer invisible.",
+            EffectKind::DetectInvisible => "You no longer see invisible things.",
+            EffectKind::Glow => "You stop glowing.",
+        }
+    }
+
+    /// As in "The sword stops glowing."
+    pub fn object_end_message(&self) -> &'static str {
+        match self {
+            EffectKind::Glow => "stops glowing.",
+            _ => "looks ordinary again.",
+        }
+    }
+}
+
+// What an effect can raise or lower while it lasts.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Stat {
+    Strength,
+    Intelligence,
+    Wisdom,
+    Dexterity,
+    Constitution,
+    // The chance to land a blow, in percent
+    Hit,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Modifier {
+    pub stat: Stat,
+    pub amount: i32,
+}
+
+// Something happening to a character or object for a number of game hours.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Effect {
+    pub kind: EffectKind,
+    pub hours: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<Modifier>,
+}
+
+impl Effect {
+    pub fn new(kind: EffectKind, hours: u32) -> Effect {
+        Effect {
+            kind,
+            hours,
+            modifier: None,
+        }
+    }
+
+    pub fn with_modifier(self, stat: Stat, amount: i32) -> Effect {
+        Effect {
+            modifier: Some(Modifier { stat, amount }),
+            ..self
+        }
+    }
+}
+
+// Everything going on with a character or object at once. There's only ever one
+// effect of each kind.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Effects(Vec<Effect>);
+
+impl Effects {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn has(&self, kind: EffectKind) -> bool {
+        self.0.iter().any(|effect| effect.kind == kind)
+    }
+
+    /// Starts an effect, or makes one that's already going last at least as long.
+    pub fn add(&mut self, effect: Effect) {
+        match self.0.iter_mut().find(|had| had.kind == effect.kind) {
+            Some(had) => had.hours = had.hours.max(effect.hours),
+            None => self.0.push(effect),
+        }
+    }
+
+    /// Ends an effect early. Returns whether there was one to end.
+    pub fn remove(&mut self, kind: EffectKind) -> bool {
+        let before = self.0.len();
+        self.0.retain(|effect| effect.kind != kind);
+        self.0.len() != before
+    }
+
+    /// Counts down an hour on every effect, returning the ones that wore off.
+    pub fn tick(&mut self) -> Vec<EffectKind> {
+        let mut ended = vec![];
+        self.0.retain_mut(|effect| {
+            effect.hours = effect.hours.saturating_sub(1);
+            if effect.hours == 0 {
+                ended.push(effect.kind);
+            }
+            effect.hours > 0
+        });
+        ended
+    }
+
+    /// The total of everything raising or lowering a stat.
+    pub fn modifier(&self, stat: Stat) -> i32 {
+        self.0
+            .iter()
+            .filter_map(|effect| effect.modifier)
+            .filter(|modifier| modifier.stat == stat)
+            .map(|modifier| modifier.amount)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Effect, EffectKind, Effects, Stat};
+
+    #[test]
+    fn wear_off() {
+        let mut effects = Effects::default();
+        effects.add(Effect::new(EffectKind::Bless, 2).with_modifier(Stat::Hit, 5));
+        effects.add(Effect::new(EffectKind::Invisible, 1));
+        effects.add(Effect::new(EffectKind::Bless, 1));
+        assert_eq!(effects.modifier(Stat::Hit), 5);
+        assert_eq!(effects.tick(), vec![EffectKind::Invisible]);
+        assert!(effects.has(EffectKind::Bless));
+        assert_eq!(effects.tick(), vec![EffectKind::Bless]);
+        assert_eq!(effects.modifier(Stat::Hit), 0);
+        assert!(effects.is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::object::{EquipSlot, Object, ObjectId, WearLocation};
+use crate::util;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+// What a character is wearing, wielding, and holding. Equipped objects aren't
+// part of the inventory.
+#[derive(Debug, Default)]
+pub struct Equipment(BTreeMap<EquipSlot, Rc<Object>>);
+
+impl Equipment {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, slot: EquipSlot) -> Option<&Rc<Object>> {
+        self.0.get(&slot)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EquipSlot, &Rc<Object>)> {
+        self.0.iter().map(|(slot, obj)| (*slot, obj))
+    }
+
+    /// The first empty slot an object worn at `location` could go into.
+    pub fn free_slot(&self, location: WearLocation) -> Option<EquipSlot> {
+        location
+            .slots()
+            .iter()
+            .find(|slot| !self.0.contains_key(slot))
+            .copied()
+    }
+
+    /// Puts an object in a slot, returning whatever was there before.
+    pub fn equip(&mut self, slot: EquipSlot, obj: Rc<Object>) -> Option<Rc<Object>> {
+        self.0.insert(slot, obj)
+    }
+
+    pub fn remove(&mut self, slot: EquipSlot) -> Option<Rc<Object>> {
+        self.0.remove(&slot)
+    }
+
+    pub fn find(&self, keyword: &str) -> Option<EquipSlot> {
+        let obj = util::find_by_keyword(self.0.values().map(|obj| &**obj), keyword)?;
+        self.0
+            .iter()
+            .find(|(_, equipped)| std::ptr::eq(&***equipped, obj))
+            .map(|(slot, _)| *slot)
+    }
+
+    pub fn contains(&self, id: ObjectId) -> bool {
+        self.0.values().any(|obj| obj.id() == id)
+    }
+
+    pub fn light(&self) -> Option<&Rc<Object>> {
+        self.0.get(&EquipSlot::Light)
+    }
+
+    pub fn wielded(&self) -> Option<&Rc<Object>> {
+        self.0.get(&EquipSlot::Wield)
+    }
+
+    /// Takes off everything at once.
+    pub fn take_all(&mut self) -> Vec<Rc<Object>> {
+        std::mem::take(&mut self.0).into_values().collect()
+    }
+}
+
+impl Display for Equipment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (slot, obj) in &self.0 {
+            write!(f, "{}{}\r\n", slot.label(), obj.name())?;
+        }
+        Ok(())
+    }
+}
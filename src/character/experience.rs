@@ -0,0 +1,125 @@
+use super::Attributes;
+use serde::{Deserialize, Serialize};
+
+// Nobody goes past this, however much they've done.
+pub const MAX_LEVEL: u32 = 50;
+
+// Each level takes this much more experience than the last.
+const EXP_PER_LEVEL: u32 = 1000;
+
+// For setting foot somewhere new.
+pub const EXPLORE_EXP: u32 = 25;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Experience {
+    pub level: u32,
+    pub exp: u32,
+}
+
+impl Default for Experience {
+    fn default() -> Experience {
+        Experience { level: 1, exp: 0 }
+    }
+}
+
+// What a character gets for each new level.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LevelGains {
+    pub hp: u32,
+    pub mana: u32,
+    pub moves: u32,
+    pub practices: u32,
+}
+
+impl Experience {
+    /// How much experience reaching `level` takes, in total.
+    pub fn exp_for_level(level: u32) -> u32 {
+        level.saturating_sub(1) * EXP_PER_LEVEL
+    }
+
+    /// How much more experience the next level takes, or None at the top.
+    pub fn exp_to_level(&self) -> Option<u32> {
+        if self.level >= MAX_LEVEL {
+            return None;
+        }
+        Some(Experience::exp_for_level(self.level + 1).saturating_sub(self.exp))
+    }
+
+    /// Adds experience, returning how many levels it was worth.
+    pub fn gain(&mut self, amount: u32) -> u32 {
+        self.exp = self.exp.saturating_add(amount);
+        let before = self.level;
+        while self.exp_to_level() == Some(0) {
+            self.level += 1;
+        }
+        self.level - before
+    }
+}
+
+/// The sturdy gain more hit points, the clever more mana, the nimble more
+/// movement, and the wise get more out of practice.
+pub fn level_gains(attributes: &Attributes) -> LevelGains {
+    let bonus = |stat: u32| stat.saturating_sub(13) / 2;
+    LevelGains {
+        hp: 8 + bonus(attributes.constitution),
+        mana: 8 + bonus(attributes.intelligence),
+        moves: 5 + bonus(attributes.dexterity),
+        practices: (attributes.wisdom / 5).max(1),
+    }
+}
+
+/// Experience for a kill, which is more for a tougher victim and next to
+/// nothing for an easy one.
+pub fn kill_exp(killer_level: u32, victim_level: u32) -> u32 {
+    let difference = victim_level as i32 - killer_level as i32;
+    (100 + 25 * difference).clamp(1, 400) as u32
+}
+
+/// What everyone at a level is known as, unless they've picked a title of their own.
+pub fn level_title(level: u32) -> &'static str {
+    match level {
+        0..=4 => "the Newcomer",
+        5..=9 => "the Wanderer",
+        10..=19 => "the Adventurer",
+        20..=29 => "the Veteran",
+        30..=39 => "the Champion",
+        40..=49 => "the Hero",
+        _ => "the Legend",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{kill_exp, level_gains, level_title, Experience, MAX_LEVEL};
+    use crate::character::Attributes;
+
+    #[test]
+    fn levels_up() {
+        let mut experience = Experience::default();
+        assert_eq!(experience.exp_to_level(), Some(1000));
+        assert_eq!(experience.gain(999), 0);
+        assert_eq!(experience.gain(1), 1);
+        assert_eq!(experience.gain(2500), 2);
+        assert_eq!(experience.level, 4);
+        assert_eq!(experience.exp_to_level(), Some(500));
+        experience.gain(u32::MAX);
+        assert_eq!(experience.level, MAX_LEVEL);
+        assert_eq!(experience.exp_to_level(), None);
+    }
+
+    #[test]
+    fn rewards() {
+        assert_eq!(kill_exp(5, 5), 100);
+        assert!(kill_exp(5, 8) > kill_exp(5, 5));
+        assert_eq!(kill_exp(40, 1), 1);
+        let sturdy = Attributes {
+            constitution: 17,
+            ..Default::default()
+        };
+        assert_eq!(level_gains(&sturdy).hp, 10);
+        assert_eq!(level_gains(&Attributes::default()).practices, 2);
+        assert_eq!(level_title(1), "the Newcomer");
+        assert_eq!(level_title(MAX_LEVEL), "the Legend");
+    }
+}
@@ -1,14 +1,67 @@
+use super::{experience, DuelRecord, Preferences};
+use crate::room::RoomId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct Player {
     pub(super) name: String,
     pub(super) password: String,
+    pub(super) admin: bool,
+    pub(super) builder_areas: Vec<String>,
+    pub(super) preferences: Preferences,
+    pub(super) duels: DuelRecord,
+    pub(super) title: Option<String>,
+    pub(super) explored: BTreeSet<RoomId>,
 }
 
 impl Player {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
+
+    pub fn is_builder(&self) -> bool {
+        self.admin || !self.builder_areas.is_empty()
+    }
+
+    pub fn preferences(&self) -> &Preferences {
+        &self.preferences
+    }
+
+    pub fn preferences_mut(&mut self) -> &mut Preferences {
+        &mut self.preferences
+    }
+
+    pub fn duels(&self) -> &DuelRecord {
+        &self.duels
+    }
+
+    pub fn duels_mut(&mut self) -> &mut DuelRecord {
+        &mut self.duels
+    }
+
+    /// The player's own title, or the one that goes with their level.
+    pub fn title(&self, level: u32) -> &str {
+        self.title
+            .as_deref()
+            .unwrap_or_else(|| experience::level_title(level))
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
+    /// Marks a room as visited. Returns whether it's the first time.
+    pub fn explore(&mut self, room_id: RoomId) -> bool {
+        self.explored.insert(room_id)
+    }
+
+    pub fn can_build(&self, area_file_name: &str) -> bool {
+        self.admin || self.builder_areas.iter().any(|area| area == area_file_name)
+    }
 }
@@ -1,17 +1,51 @@
-use crate::character::{Character, CharacterData, Player, Pronoun};
-use crate::object::Object;
+use crate::character::{
+    Character, CharacterData, DuelRecord, Player, Position, Preferences, Pronoun,
+};
+use crate::object::{EquipSlot, Object};
+use crate::room::RoomId;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerRecord {
     name: String,
     password: String,
-    character: CharacterData,
     #[serde(default)]
-    inventory: Vec<Object>,
+    admin: bool,
+    #[serde(default)]
+    builder_areas: Vec<String>,
+    #[serde(default)]
+    preferences: Preferences,
+    #[serde(default, skip_serializing_if = "DuelRecord::is_empty")]
+    duels: DuelRecord,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    // Every room they've been to, for exploration experience
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    explored: BTreeSet<RoomId>,
+    character: CharacterData,
+    // Objects hold their contents in `Rc`s, which can't leave the thread they were
+    // made on. A record travels from the login thread to the game thread, so it
+    // carries its objects as plain JSON and only builds them on the game thread.
+    #[serde(default = "empty_list")]
+    inventory: Value,
+    #[serde(default = "empty_map")]
+    equipment: Value,
+}
+
+fn empty_list() -> Value {
+    Value::Array(vec![])
+}
+
+fn empty_map() -> Value {
+    Value::Object(Default::default())
 }
 
+// Everything a player has on them: their inventory, and what they wear in each slot.
+type Carried = (Vec<Object>, BTreeMap<EquipSlot, Object>);
+
 impl PlayerRecord {
     pub fn file_path(name: &str) -> PathBuf {
         Path::new("players").join(name).with_extension("json")
@@ -23,19 +57,43 @@ impl PlayerRecord {
         PlayerRecord {
             name,
             password,
+            admin: false,
+            builder_areas: vec![],
+            preferences: Preferences::default(),
+            duels: DuelRecord::default(),
+            title: None,
+            explored: BTreeSet::new(),
             character: CharacterData::new_player(keywords, formal_name, pronoun),
-            inventory: vec![],
+            inventory: empty_list(),
+            equipment: empty_map(),
         }
     }
 
-    pub fn from_player(player: &Player, character: &Character) -> PlayerRecord {
-        let inventory = character.inventory.iter().cloned().collect();
-        PlayerRecord {
+    pub fn from_player(player: &Player, character: &Character) -> std::io::Result<PlayerRecord> {
+        let inventory: Vec<&Object> = character.inventory.iter().collect();
+        let equipment: BTreeMap<EquipSlot, &Object> = character
+            .equipment
+            .iter()
+            .map(|(slot, obj)| (slot, &**obj))
+            .collect();
+        // Fights don't survive a save, so don't write one down.
+        let mut data = character.data.clone();
+        if data.position == Position::Fighting {
+            data.position = Position::Standing;
+        }
+        Ok(PlayerRecord {
             name: player.name.clone(),
             password: player.password.clone(),
-            character: character.data.clone(),
-            inventory,
-        }
+            admin: player.admin,
+            builder_areas: player.builder_areas.clone(),
+            preferences: player.preferences.clone(),
+            duels: player.duels.clone(),
+            title: player.title.clone(),
+            explored: player.explored.clone(),
+            character: data,
+            inventory: serde_json::to_value(inventory)?,
+            equipment: serde_json::to_value(equipment)?,
+        })
     }
 
     pub fn name(self) -> String {
@@ -46,13 +104,27 @@ impl PlayerRecord {
         &self.password
     }
 
-    pub fn into_inner(self) -> (Player, CharacterData, Vec<Object>) {
+    /// Makes sure everything the player is carrying can be read back in.
+    pub fn check_objects(&self) -> serde_json::Result<()> {
+        Vec::<Object>::deserialize(&self.inventory)?;
+        BTreeMap::<EquipSlot, Object>::deserialize(&self.equipment)?;
+        Ok(())
+    }
+
+    /// Splits the record up, building its objects.
+    pub fn into_inner(self) -> serde_json::Result<(Player, CharacterData, Carried)> {
+        let inventory = serde_json::from_value(self.inventory)?;
+        let equipment = serde_json::from_value(self.equipment)?;
         let player = Player {
             name: self.name,
             password: self.password,
+            admin: self.admin,
+            builder_areas: self.builder_areas,
+            preferences: self.preferences,
+            duels: self.duels,
+            title: self.title,
+            explored: self.explored,
         };
-        let character = self.character;
-        let inventory = self.inventory;
-        (player, character, inventory)
+        Ok((player, self.character, (inventory, equipment)))
     }
 }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+// From least to most able to act, so that positions can be compared against a
+// command's minimum.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Position {
+    Dead,
+    Sleeping,
+    Resting,
+    Sitting,
+    Fighting,
+    #[default]
+    Standing,
+}
+
+impl Position {
+    pub const ALL: &'static [Position] = &[
+        Position::Dead,
+        Position::Sleeping,
+        Position::Resting,
+        Position::Sitting,
+        Position::Fighting,
+        Position::Standing,
+    ];
+
+    pub fn parse(name: &str) -> Option<Position> {
+        Position::ALL
+            .iter()
+            .find(|position| format!("{:?}", position).eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub fn is_awake(&self) -> bool {
+        *self > Position::Sleeping
+    }
+
+    /// As in "Rhane is sitting here."
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Position::Dead => "lying dead",
+            Position::Sleeping => "sleeping",
+            Position::Resting => "resting",
+            Position::Sitting => "sitting",
+            Position::Fighting => "fighting",
+            Position::Standing => "standing",
+        }
+    }
+
+    /// Why someone in this position can't do something that needs them to be up
+    /// and about.
+    pub fn refusal(&self) -> &'static str {
+        match self {
+            Position::Dead => "Lie still; you are DEAD.",
+            Position::Sleeping => "You can't do that while asleep.",
+            Position::Resting => "You can't do that while resting.",
+            Position::Sitting => "Better stand up first.",
+            Position::Fighting => "No way! You are still fighting!",
+            Position::Standing => "You can't do that right now.",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Position;
+
+    #[test]
+    fn ordered_by_ability() {
+        assert!(Position::Sleeping < Position::Resting);
+        assert!(Position::Fighting < Position::Standing);
+        assert!(!Position::Sleeping.is_awake());
+        assert!(Position::Sitting.is_awake());
+    }
+}
@@ -0,0 +1,21 @@
+use super::vitals::DEFAULT_PROMPT;
+use serde::{Deserialize, Serialize};
+
+// Settings a player can toggle for themselves. Anything missing from an older
+// player file gets its default.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Preferences {
+    pub auto_exits: bool,
+    // See `Character::prompt` for what can go in it
+    pub prompt: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Preferences {
+        Preferences {
+            auto_exits: true,
+            prompt: String::from(DEFAULT_PROMPT),
+        }
+    }
+}
@@ -38,7 +38,7 @@ impl Pronoun {
         match self {
             Pronoun::It => "its",
             Pronoun::He => "his",
-            Pronoun::She => "hers",
+            Pronoun::She => "her",
             Pronoun::They => "their",
         }
     }
@@ -0,0 +1,80 @@
+use crate::object::{Object, ObjectId, ObjectType};
+use serde::{Deserialize, Serialize};
+
+// What a shopkeeper deals in and when. Prices are percentages of an object's value.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Shop {
+    // Kept on hand, and brought back whenever the area resets
+    pub stock: Vec<ObjectId>,
+    // The kinds of things the keeper will buy from players
+    pub buys: Vec<ObjectType>,
+    pub sells_at: u32,
+    pub buys_at: u32,
+    // Hours of the game day, where closing before opening means open overnight
+    pub opens: u64,
+    pub closes: u64,
+}
+
+impl Default for Shop {
+    fn default() -> Shop {
+        Shop {
+            stock: vec![],
+            buys: vec![],
+            sells_at: 120,
+            buys_at: 80,
+            opens: 0,
+            closes: 24,
+        }
+    }
+}
+
+impl Shop {
+    pub fn is_open(&self, hour: u64) -> bool {
+        if self.opens <= self.closes {
+            self.opens <= hour && hour < self.closes
+        } else {
+            hour >= self.opens || hour < self.closes
+        }
+    }
+
+    /// What a player pays for something. Nothing is ever free.
+    pub fn selling_price(&self, obj: &Object) -> u32 {
+        (obj.value() * self.sells_at / 100).max(1)
+    }
+
+    /// What the keeper would pay for something, if they want it at all.
+    pub fn buying_price(&self, obj: &Object) -> Option<u32> {
+        if !self.buys.contains(&obj.object_type()) {
+            return None;
+        }
+        Some(obj.value() * self.buys_at / 100).filter(|price| *price > 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Shop;
+
+    #[test]
+    fn opening_hours() {
+        let day = Shop {
+            opens: 6,
+            closes: 20,
+            ..Default::default()
+        };
+        assert!(!day.is_open(5));
+        assert!(day.is_open(6));
+        assert!(!day.is_open(20));
+
+        let night = Shop {
+            opens: 20,
+            closes: 6,
+            ..Default::default()
+        };
+        assert!(night.is_open(23));
+        assert!(night.is_open(0));
+        assert!(!night.is_open(12));
+        assert!(Shop::default().is_open(23));
+    }
+}
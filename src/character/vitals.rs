@@ -0,0 +1,174 @@
+use super::{Effects, Position, Stat};
+use serde::{Deserialize, Serialize};
+
+// Hit points, mana, and movement, each with a maximum to regenerate back up to.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Vitals {
+    pub hp: u32,
+    pub max_hp: u32,
+    pub mana: u32,
+    pub max_mana: u32,
+    pub moves: u32,
+    pub max_moves: u32,
+}
+
+// The same as a new Merc character.
+impl Default for Vitals {
+    fn default() -> Vitals {
+        Vitals {
+            hp: 20,
+            max_hp: 20,
+            mana: 100,
+            max_mana: 100,
+            moves: 100,
+            max_moves: 100,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Attributes {
+    pub strength: u32,
+    pub intelligence: u32,
+    pub wisdom: u32,
+    pub dexterity: u32,
+    pub constitution: u32,
+}
+
+impl Default for Attributes {
+    fn default() -> Attributes {
+        Attributes {
+            strength: 13,
+            intelligence: 13,
+            wisdom: 13,
+            dexterity: 13,
+            constitution: 13,
+        }
+    }
+}
+
+impl Attributes {
+    /// These attributes raised or lowered by effects, but never below 1.
+    pub fn modified(&self, effects: &Effects) -> Attributes {
+        let apply = |base: u32, stat: Stat| (base as i32 + effects.modifier(stat)).max(1) as u32;
+        Attributes {
+            strength: apply(self.strength, Stat::Strength),
+            intelligence: apply(self.intelligence, Stat::Intelligence),
+            wisdom: apply(self.wisdom, Stat::Wisdom),
+            dexterity: apply(self.dexterity, Stat::Dexterity),
+            constitution: apply(self.constitution, Stat::Constitution),
+        }
+    }
+}
+
+impl Vitals {
+    /// Spends movement points, if there are enough of them.
+    pub fn spend_moves(&mut self, cost: u32) -> bool {
+        if self.moves < cost {
+            return false;
+        }
+        self.moves -= cost;
+        true
+    }
+
+    /// Recovers a game hour's worth. Lying down helps, and going hungry, thirsty,
+    /// or poisoned slows it down.
+    pub fn regenerate(&mut self, position: Position, attributes: &Attributes, slowed: bool) {
+        let (numerator, denominator) = match position {
+            Position::Dead | Position::Fighting => return,
+            Position::Sleeping => (2, 1),
+            Position::Resting | Position::Sitting => (3, 2),
+            Position::Standing => (1, 1),
+        };
+        let gain = |base: u32| {
+            let gain = base * numerator / denominator;
+            if slowed {
+                gain / 2
+            } else {
+                gain
+            }
+        };
+        let hp = gain(attributes.constitution / 2 + self.max_hp / 10);
+        let mana = gain((attributes.intelligence + attributes.wisdom) / 4 + self.max_mana / 10);
+        let moves = gain(attributes.dexterity / 2 + self.max_moves / 10);
+        self.hp = (self.hp + hp).min(self.max_hp);
+        self.mana = (self.mana + mana).min(self.max_mana);
+        self.moves = (self.moves + moves).min(self.max_moves);
+    }
+}
+
+pub const DEFAULT_PROMPT: &str = "<%hhp %mm %vmv> ";
+
+/// Fills in a prompt. %h, %m, and %v are current hit points, mana, and movement,
+/// with capitals for the maximums, and %g is gold.
+pub fn render_prompt(format: &str, vitals: &Vitals, gold: u32) -> String {
+    let mut prompt = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            prompt.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('h') => prompt.push_str(&vitals.hp.to_string()),
+            Some('H') => prompt.push_str(&vitals.max_hp.to_string()),
+            Some('m') => prompt.push_str(&vitals.mana.to_string()),
+            Some('M') => prompt.push_str(&vitals.max_mana.to_string()),
+            Some('v') => prompt.push_str(&vitals.moves.to_string()),
+            Some('V') => prompt.push_str(&vitals.max_moves.to_string()),
+            Some('g') => prompt.push_str(&gold.to_string()),
+            Some(other) => prompt.push(other),
+            None => prompt.push('%'),
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod test {
+    use super::{render_prompt, Attributes, Vitals};
+    use crate::character::{Effect, EffectKind, Effects, Position, Stat};
+
+    #[test]
+    fn regenerates_faster_asleep() {
+        let tired = Vitals {
+            hp: 1,
+            mana: 0,
+            moves: 0,
+            ..Default::default()
+        };
+        let mut standing = tired;
+        standing.regenerate(Position::Standing, &Attributes::default(), false);
+        let mut sleeping = tired;
+        sleeping.regenerate(Position::Sleeping, &Attributes::default(), false);
+        assert!(sleeping.hp > standing.hp);
+        assert!(sleeping.hp <= sleeping.max_hp);
+
+        let mut fighting = tired;
+        fighting.regenerate(Position::Fighting, &Attributes::default(), false);
+        assert_eq!(fighting, tired);
+    }
+
+    #[test]
+    fn modified_attributes() {
+        let mut effects = Effects::default();
+        effects.add(Effect::new(EffectKind::GiantStrength, 8).with_modifier(Stat::Strength, 2));
+        effects.add(Effect::new(EffectKind::Poison, 8).with_modifier(Stat::Dexterity, -20));
+        let modified = Attributes::default().modified(&effects);
+        assert_eq!(modified.strength, 15);
+        assert_eq!(modified.dexterity, 1);
+        assert_eq!(modified.wisdom, 13);
+    }
+
+    #[test]
+    fn prompt_tokens() {
+        let vitals = Vitals::default();
+        assert_eq!(
+            render_prompt("<%h/%Hhp %v/%Vmv %g gold> %", &vitals, 7),
+            "<20/20hp 100/100mv 7 gold> %"
+        );
+        assert_eq!(render_prompt("%%", &vitals, 0), "%");
+    }
+}
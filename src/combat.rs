@@ -0,0 +1,161 @@
+use crate::character::{Attributes, Vitals};
+use crate::util;
+
+/// Rolls whether an attack lands. Quicker hands hit more often and get hit less,
+/// and a bonus from effects like bless helps too.
+pub fn hits(attacker: &Attributes, bonus: i32, victim: &Attributes) -> bool {
+    util::random_percent() <= hit_chance(attacker, bonus, victim)
+}
+
+// Out of 100, and never a sure thing either way.
+fn hit_chance(attacker: &Attributes, bonus: i32, victim: &Attributes) -> u32 {
+    let chance = 70 + bonus + 3 * (attacker.dexterity as i32 - victim.dexterity as i32);
+    chance.clamp(5, 95) as u32
+}
+
+/// Rolls how hard a landed attack hits. A weapon beats bare hands, and the strong
+/// hit harder either way.
+pub fn roll_damage(attacker: &Attributes, armed: bool) -> u32 {
+    let (low, high) = if armed { (2, 8) } else { (1, 4) };
+    util::random_range(low, high) + attacker.strength.saturating_sub(13) / 2
+}
+
+// A duellist who's been beaten down to this share of their hit points has to yield.
+const YIELD_PERCENT: u32 = 20;
+
+/// Whether a hit leaving someone with `hp` is enough to win a duel against them.
+pub fn must_yield(hp: u32, max_hp: u32) -> bool {
+    hp <= max_hp * YIELD_PERCENT / 100
+}
+
+/// A rough measure of how well someone would do in a duel, as they are right now.
+pub fn duel_strength(vitals: &Vitals, attributes: &Attributes, armed: bool) -> u32 {
+    let armed = if armed { 5 } else { 0 };
+    vitals.hp + attributes.strength + attributes.dexterity + attributes.constitution + armed
+}
+
+/// How a rival measures up, for `consider`.
+pub fn assess(yours: u32, theirs: u32, rival: &str) -> String {
+    let rival = util::capitalize(rival);
+    match theirs * 100 / yours.max(1) {
+        0..=49 => format!("{} would be no match for you.", rival),
+        50..=79 => format!("You should have the upper hand against {}.", rival),
+        80..=120 => format!("{} looks like a fair match.", rival),
+        121..=150 => format!("{} would give you a hard time.", rival),
+        _ => format!("{} would wipe the floor with you.", rival),
+    }
+}
+
+// The same words as Merc, for how bad a hit was.
+fn damage_verb(damage: u32) -> (&'static str, &'static str) {
+    match damage {
+        0 => ("miss", "misses"),
+        1..=4 => ("scratch", "scratches"),
+        5..=8 => ("graze", "grazes"),
+        9..=12 => ("hit", "hits"),
+        13..=16 => ("injure", "injures"),
+        17..=20 => ("wound", "wounds"),
+        21..=24 => ("maul", "mauls"),
+        25..=28 => ("decimate", "decimates"),
+        29..=32 => ("devastate", "devastates"),
+        33..=36 => ("maim", "maims"),
+        37..=40 => ("MUTILATE", "MUTILATES"),
+        41..=44 => ("DISEMBOWEL", "DISEMBOWELS"),
+        45..=48 => ("EVISCERATE", "EVISCERATES"),
+        49..=52 => ("MASSACRE", "MASSACRES"),
+        53..=100 => ("*** DEMOLISH ***", "*** DEMOLISHES ***"),
+        _ => ("*** ANNIHILATE ***", "*** ANNIHILATES ***"),
+    }
+}
+
+/// What the attacker, the victim, and everyone else watching see of one attack,
+/// which might be named, like a spell.
+pub fn damage_messages(
+    damage: u32,
+    attack: Option<&str>,
+    attacker: &str,
+    victim: &str,
+) -> (String, String, String) {
+    let (verb, verbs) = damage_verb(damage);
+    let punctuation = if damage > 24 { '!' } else { '.' };
+    let attacker = util::capitalize(attacker);
+    match attack {
+        Some(attack) => (
+            format!("Your {} {} {}{}", attack, verbs, victim, punctuation),
+            format!("{}'s {} {} you{}", attacker, attack, verbs, punctuation),
+            format!(
+                "{}'s {} {} {}{}",
+                attacker, attack, verbs, victim, punctuation
+            ),
+        ),
+        None => (
+            format!("You {} {}{}", verb, victim, punctuation),
+            format!("{} {} you{}", attacker, verbs, punctuation),
+            format!("{} {} {}{}", attacker, verbs, victim, punctuation),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{assess, damage_messages, hit_chance, must_yield, roll_damage};
+    use crate::character::Attributes;
+
+    #[test]
+    fn hit_chance_bounds() {
+        let average = Attributes::default();
+        let nimble = Attributes {
+            dexterity: 40,
+            ..Default::default()
+        };
+        assert_eq!(hit_chance(&average, 0, &average), 70);
+        assert_eq!(hit_chance(&average, 5, &average), 75);
+        assert_eq!(hit_chance(&nimble, 0, &average), 95);
+        assert_eq!(hit_chance(&average, 0, &nimble), 5);
+    }
+
+    #[test]
+    fn damage_rolls() {
+        let strong = Attributes {
+            strength: 19,
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            assert!((1..=4).contains(&roll_damage(&Attributes::default(), false)));
+            assert!((5..=11).contains(&roll_damage(&strong, true)));
+        }
+    }
+
+    #[test]
+    fn yielding() {
+        assert!(must_yield(4, 20));
+        assert!(!must_yield(5, 20));
+        assert!(must_yield(0, 1));
+    }
+
+    #[test]
+    fn assessments() {
+        assert_eq!(assess(100, 100, "Rhane"), "Rhane looks like a fair match.");
+        assert_eq!(assess(100, 40, "Rhane"), "Rhane would be no match for you.");
+        assert_eq!(
+            assess(0, 40, "Rhane"),
+            "Rhane would wipe the floor with you."
+        );
+    }
+
+    #[test]
+    fn messages() {
+        let (you, them, room) = damage_messages(0, None, "the guard", "Rhane");
+        assert_eq!(you, "You miss Rhane.");
+        assert_eq!(them, "The guard misses you.");
+        assert_eq!(room, "The guard misses Rhane.");
+        assert_eq!(
+            damage_messages(30, None, "Rhane", "the guard").0,
+            "You devastate the guard!"
+        );
+        assert_eq!(
+            damage_messages(10, Some("magic missile"), "Rhane", "the guard").1,
+            "Rhane's magic missile hits you."
+        );
+    }
+}
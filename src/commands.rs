@@ -1,8 +1,20 @@
+mod admin;
+mod books;
+mod combat;
+mod doors;
+mod duel;
+mod equipment;
+mod food;
 mod informational;
 mod misc;
 mod movement;
 mod objects;
+mod olc;
+mod position;
+mod shops;
+mod skills;
 
+use crate::character::Position;
 use crate::room::RoomId;
 use crate::util;
 use crate::World;
@@ -11,56 +23,66 @@ use std::io::Result as IoResult;
 
 pub type CommandFn = fn(Index, RoomId, &str, &mut World) -> IoResult<()>;
 
-const COMMANDS: &[(&'static str, CommandFn)] = &[
+// Input from a connection that's in an OLC editor goes here instead of through `COMMANDS`.
+pub static EDITOR_INPUT: CommandFn = olc::interpret;
+// And input from someone writing in a book goes here.
+pub static WRITING_INPUT: CommandFn = books::write_line;
+
+// Each command has the lowest position a character can be in and still use it.
+const COMMANDS: &[(&'static str, CommandFn, Position)] = &[
     // Movement commands
-    ("north", movement::north),
-    ("south", movement::south),
-    ("east", movement::east),
-    ("west", movement::west),
-    ("up", movement::up),
-    ("down", movement::down),
+    ("north", movement::north, Position::Standing),
+    ("south", movement::south, Position::Standing),
+    ("east", movement::east, Position::Standing),
+    ("west", movement::west, Position::Standing),
+    ("up", movement::up, Position::Standing),
+    ("down", movement::down, Position::Standing),
+    ("enter", movement::enter, Position::Standing),
 
     // Common commands
-    // ("buy", buy),
-    // ("cast", cast),
-    // ("exits", exits),
-    ("get", objects::get),
-    ("inventory", informational::inventory),
-    // ("kill", kill),
-    // ("fight", fight),
-    ("look", informational::look),
+    ("buy", shops::buy, Position::Resting),
+    ("borrow", books::borrow, Position::Resting),
+    ("browse", books::browse, Position::Resting),
+    ("cast", skills::cast, Position::Fighting),
+    ("exits", informational::exits, Position::Resting),
+    ("get", objects::get, Position::Resting),
+    ("inventory", informational::inventory, Position::Dead),
+    ("kill", combat::kill, Position::Fighting),
+    ("fight", combat::fight, Position::Fighting),
+    ("look", informational::look, Position::Resting),
     // ("order", order),
-    // ("rest", rest),
-    // ("sleep", sleep),
-    // ("stand", stand),
+    ("rest", position::rest, Position::Sleeping),
+    ("sleep", position::sleep, Position::Sleeping),
+    ("stand", position::stand, Position::Sleeping),
     // ("tell", tell),
-    // ("wield", wield),
+    ("wield", equipment::wield, Position::Resting),
     // ("wizhelp", wizhelp),
-    ("go", movement::go),
+    ("go", movement::go, Position::Standing),
 
     // Informational commands
     // ("areas", areas),
     // ("commands", commands),
     // ("compare", compare),
-    // ("consider", consider),
+    ("consider", duel::consider, Position::Resting),
     // ("credits", credits),
-    // ("equipment", equipment),
+    ("equipment", informational::equipment, Position::Dead),
     // ("examine", examine),
     // ("help", help),
     // ("report", report),
     // ("pagelength", pagelength),
-    // ("score", score),
-    // ("slist", slist),
+    ("score", informational::score, Position::Dead),
+    ("slist", skills::slist, Position::Dead),
     // ("socials", socials),
-    // ("time", time),
-    // ("weather", weather),
-    // ("who", who),
+    ("time", informational::time, Position::Dead),
+    ("weather", informational::weather, Position::Resting),
+    ("who", informational::who, Position::Dead),
     // ("wizlist", wizlist),
 
     // Configuration commands
+    ("autoexits", misc::autoexits, Position::Dead),
     // ("password", password),
-    // ("prmopt", prompt),
-    // ("title", title),
+    ("prompt", misc::prompt, Position::Dead),
+    ("title", misc::title, Position::Dead),
 
     // Communication commands
     // ("chat", chat),
@@ -75,49 +97,70 @@ const COMMANDS: &[(&'static str, CommandFn)] = &[
     // ("yell", yell),
 
     // Object manipulation commands
-    // ("close", close),
-    // ("drink", drink),
-    ("drop", objects::drop),
-    // ("eat", eat),
-    // ("fill", fill),
-    ("give", objects::give),
-    // ("hold", hold),
-    // ("list", list),
-    // ("lock", lock),
-    // ("open", open),
-    // ("pick", pick),
-    // ("put", put),
-    // ("quaff", quaff),
+    ("close", doors::close, Position::Resting),
+    ("drink", food::drink, Position::Resting),
+    ("drop", objects::drop, Position::Resting),
+    ("eat", food::eat, Position::Resting),
+    ("fill", food::fill, Position::Resting),
+    ("give", objects::give, Position::Resting),
+    ("hold", equipment::hold, Position::Resting),
+    ("list", shops::list, Position::Resting),
+    ("lock", doors::lock, Position::Resting),
+    ("open", doors::open, Position::Resting),
+    ("pick", doors::pick, Position::Resting),
+    ("put", objects::put, Position::Resting),
+    ("quaff", food::quaff, Position::Resting),
+    ("read", books::read, Position::Resting),
     // ("recite", recite),
-    // ("remove", remove),
-    // ("sell", sell),
-    ("take", objects::take),
-    // ("unlock", unlock),
-    // ("value", value),
-    // ("wear", wear),
+    ("remove", equipment::remove, Position::Resting),
+    ("sell", shops::sell, Position::Resting),
+    ("shelve", books::shelve, Position::Resting),
+    ("take", objects::take, Position::Resting),
+    ("unlock", doors::unlock, Position::Resting),
+    ("value", shops::value, Position::Resting),
+    ("wear", equipment::wear, Position::Resting),
+    ("write", books::write, Position::Resting),
     // ("zap", zap),
 
     // Combat commands
-    // ("flee", flee),
-    // ("rescue", rescue),
+    ("flee", combat::flee, Position::Fighting),
+    ("rescue", combat::rescue, Position::Fighting),
+    ("challenge", duel::challenge, Position::Standing),
+    ("accept", duel::accept, Position::Standing),
+    ("yield", duel::yield_duel, Position::Fighting),
 
     // Misc commands
     // ("follow", follow),
     // ("hide", hide),
+    ("practice", skills::practice, Position::Resting),
+    ("recall", movement::recall, Position::Standing),
+    ("search", movement::search, Position::Standing),
+    ("sit", position::sit, Position::Sleeping),
     // ("qui", quit_mistake),
-    ("quit", misc::quit),
-    ("save", misc::save),
+    ("quit", misc::quit, Position::Dead),
+    ("save", misc::save, Position::Dead),
     // ("sneak", sneak),
     // ("steal", steal),
     // ("visible", visible),
-    // ("wake", wake),
+    ("wake", position::wake, Position::Sleeping),
     // ("where", where),
+
+    // Admin commands
+    ("areareload", admin::areareload, Position::Dead),
+
+    // Builder commands
+    ("redit", olc::redit, Position::Dead),
+    ("oedit", olc::oedit, Position::Dead),
+    ("medit", olc::medit, Position::Dead),
 ];
 
-pub fn lookup_command(command: &str) -> Option<&'static CommandFn> {
+pub fn lookup_command(command: &str) -> Option<(&'static CommandFn, Position)> {
     if command.is_empty() {
         return None;
     }
     let command = command.to_ascii_lowercase();
-    util::find_partial(COMMANDS.iter().map(|(k, v)| (k, v)), &command)
+    util::find_partial(
+        COMMANDS.iter().map(|(k, v, position)| (k, (v, *position))),
+        &command,
+    )
 }
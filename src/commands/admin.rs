@@ -0,0 +1,44 @@
+use crate::area::AreaLoadError;
+use crate::room::RoomId;
+use crate::util;
+use crate::world::World;
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+
+pub fn areareload(
+    conn_idx: Index,
+    _room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    if !conn.player().is_admin() {
+        return write!(conn, "I have no idea what that means!\r\n");
+    }
+
+    let (area_name, _) = util::take_argument(arguments);
+    let area_name = match area_name {
+        Some(name) => name.to_string(),
+        None => {
+            write!(conn, "Reload which area?\r\n")?;
+            for area in &world.areas {
+                write!(conn, "    {:<16} {}\r\n", area.file_name, area.name())?;
+            }
+            return Ok(());
+        }
+    };
+
+    let result = world.reload_area(&area_name);
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    match result {
+        Ok(summary) => write!(conn, "Reloaded {}: {}.\r\n", area_name, summary),
+        Err(AreaLoadError::Unknown(_)) => write!(conn, "There's no area called {}.\r\n", area_name),
+        Err(e) => write!(conn, "Couldn't reload {}: {:?}\r\n", area_name, e),
+    }
+}
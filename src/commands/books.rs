@@ -0,0 +1,327 @@
+use crate::object::{Object, ObjectType, Page, Writing, MAX_PAGE_LENGTH};
+use crate::room::{RoomFlag, RoomId};
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+
+fn char_name(char_idx: Index, world: &World) -> String {
+    world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string()
+}
+
+fn is_library(room_id: RoomId, world: &World) -> bool {
+    world
+        .rooms
+        .get(&room_id)
+        .is_some_and(|room| room.has_flag(RoomFlag::Library))
+}
+
+// Books can be read wherever they are: carried, lying around, or on a shelf.
+fn find_book<'a>(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+    world: &'a World,
+) -> Result<&'a Object, String> {
+    let char = world.characters.get(char_idx).unwrap();
+    let obj = util::find_item_by_keyword(&char.inventory, keyword)
+        .or_else(|| util::find_item_by_keyword(&world.room_objs[&room_id], keyword))
+        .or_else(|| {
+            world
+                .shelves
+                .get(&room_id)
+                .and_then(|shelf| util::find_item_by_keyword(shelf, keyword))
+        })
+        .ok_or_else(|| format!("You don't see any {} here.", keyword))?;
+    if obj.object_type() != ObjectType::Book {
+        return Err(String::from("You can't read that."));
+    }
+    Ok(obj)
+}
+
+fn contents(obj: &Object, keyword: &str) -> String {
+    let pages = obj
+        .book()
+        .map(|book| book.pages.borrow().clone())
+        .unwrap_or_default();
+    if pages.is_empty() {
+        return format!("{} is blank.", util::capitalize(obj.name()));
+    }
+    let mut text = format!("{} contains:\r\n", util::capitalize(obj.name()));
+    for (idx, page) in pages.iter().enumerate() {
+        text.push_str(&format!("  {:2}. {}\r\n", idx + 1, page.title));
+    }
+    text.push_str(&format!("Read {} <page> to turn to a page.", keyword));
+    text
+}
+
+fn page(obj: &Object, keyword: &str, number: usize) -> String {
+    let (page, count) = match obj.book() {
+        Some(book) => (book.page(number), book.page_count()),
+        None => (None, 0),
+    };
+    let page = match page {
+        Some(page) => page,
+        None => return format!("There's no page {} in {}.", number, obj.name()),
+    };
+    let mut text = format!(
+        "{}, page {} of {}: {}\r\n\r\n{}\r\n",
+        util::capitalize(obj.name()),
+        number,
+        count,
+        page.title,
+        page.text.trim_end()
+    );
+    if number < count {
+        text.push_str(&format!(
+            "\r\n(Read {} {} for the next page.)",
+            keyword,
+            number + 1
+        ));
+    }
+    text
+}
+
+pub fn read(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (keyword, rest) = util::take_argument(arguments);
+    let message = if !world.can_see_room(char_idx, room_id) {
+        String::from("It's too dark to read.")
+    } else {
+        match keyword {
+            None => String::from("Read what?"),
+            Some(keyword) => match find_book(char_idx, room_id, keyword, world) {
+                Err(message) => message,
+                Ok(obj) => match util::take_argument(rest).0 {
+                    None => contents(obj, keyword),
+                    Some(number) => match number.parse::<usize>() {
+                        Ok(number) => page(obj, keyword, number),
+                        Err(_) => String::from("Which page (by number)?"),
+                    },
+                },
+            },
+        }
+    };
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    write!(conn, "{}\r\n", message)
+}
+
+pub fn write(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (keyword, title) = util::take_argument(arguments);
+    let result = keyword.ok_or("Write in what?").and_then(|keyword| {
+        let char = world.characters.get(char_idx).unwrap();
+        let obj = util::find_item_by_keyword(&char.inventory, keyword)
+            .ok_or("You aren't carrying that.")?;
+        let book = obj
+            .book()
+            .filter(|book| book.writable)
+            .ok_or("You can't write in that.")?;
+        if book.is_full() {
+            return Err("There's no room left to write in it.");
+        }
+        if title.trim().is_empty() {
+            return Err("What's the title of the page?");
+        }
+        Ok((obj.instance(), obj.name().to_string()))
+    });
+    let (book, book_name) = match result {
+        Ok(found) => found,
+        Err(message) => {
+            world.msg_char(message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    let char_name = char_name(char_idx, world);
+    let title = title.trim().to_string();
+    world.msg_char(
+        &format!(
+            "You begin a page titled '{}' in {}. End it with a line containing only @ (an empty page is thrown away)",
+            title, book_name
+        ),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} starts writing in {}.", char_name, book_name),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    conn.writing = Some(Writing {
+        book,
+        title,
+        text: String::new(),
+    });
+    Ok(())
+}
+
+/// Takes a line of a page being written, and puts the page in the book once it's done.
+pub fn write_line(
+    conn_idx: Index,
+    _room_id: RoomId,
+    input: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let writing = match conn.writing.as_mut() {
+        Some(writing) => writing,
+        None => return Ok(()),
+    };
+    let line = input.trim_end_matches(&['\r', '\n'][..]);
+    if line.trim() != "@" {
+        if writing.text.chars().count() + line.chars().count() + 1 > MAX_PAGE_LENGTH {
+            return write!(
+                conn,
+                "There's no room left on the page. End it with a line containing only @.\r\n"
+            );
+        }
+        writing.text.push_str(line);
+        writing.text.push('\n');
+        return Ok(());
+    }
+
+    let writing = conn.writing.take().unwrap();
+    let char = world.characters.get(conn.character).unwrap();
+    let book = char
+        .inventory
+        .iter()
+        .find(|obj| obj.instance() == writing.book)
+        .and_then(|obj| obj.book());
+    let message = match book {
+        None => String::from("You no longer have anything to write in."),
+        Some(_) if writing.text.trim().is_empty() => String::from("You leave the page blank."),
+        Some(book) if book.is_full() => String::from("There's no room left to write in it."),
+        Some(book) => {
+            book.pages.borrow_mut().push(Page {
+                title: writing.title,
+                text: writing.text,
+            });
+            format!("You finish page {}.", book.page_count())
+        }
+    };
+    write!(conn, "{}\r\n", message)
+}
+
+pub fn shelve(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    if !is_library(room_id, world) {
+        world.msg_char("There are no shelves here.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Shelve what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = world.characters.get_mut(char_idx).unwrap();
+    let obj = match util::pluck_item_from_list(&mut char.inventory, keyword) {
+        Some(obj) if obj.object_type() == ObjectType::Book => obj,
+        Some(obj) => {
+            char.inventory.push_front(obj);
+            world.msg_char(
+                "Only books go on the shelves.",
+                Recipient::Subject(char_idx),
+            );
+            return Ok(());
+        }
+        None => {
+            world.msg_char("You aren't carrying that.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    let char_name = char_name(char_idx, world);
+    world.msg_char(
+        &format!("You put {} on a shelf.", obj.name()),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} puts {} on a shelf.", char_name, obj.name()),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    world.shelves.entry(room_id).or_default().push_front(obj);
+    Ok(())
+}
+
+pub fn borrow(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    if !is_library(room_id, world) {
+        world.msg_char("There are no shelves here.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Borrow what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let shelf = world.shelves.entry(room_id).or_default();
+    let obj = match util::pluck_item_from_list(shelf, keyword) {
+        Some(obj) => obj,
+        None => {
+            world.msg_char("That isn't on the shelves.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = world.characters.get_mut(char_idx).unwrap();
+    if let Err(refusal) = char.can_carry(&obj, &[]) {
+        world.shelves.get_mut(&room_id).unwrap().push_front(obj);
+        world.msg_char(&refusal, Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    let you = format!("You take {} down from a shelf.", obj.name());
+    let they = format!(
+        "{} takes {} down from a shelf.",
+        char.formal_name(),
+        obj.name()
+    );
+    char.inventory.push_front(obj);
+    world.msg_char(&you, Recipient::Subject(char_idx));
+    world.msg_char(&they, Recipient::NotSubject(char_idx, room_id));
+    Ok(())
+}
+
+pub fn browse(
+    conn_idx: Index,
+    room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let shelved: Vec<String> = world
+        .shelves
+        .get(&room_id)
+        .map(|shelf| shelf.iter().map(|obj| obj.name().to_string()).collect())
+        .unwrap_or_default();
+    let library = is_library(room_id, world);
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    if !library {
+        return write!(conn, "There are no shelves here.\r\n");
+    }
+    if shelved.is_empty() {
+        return write!(conn, "The shelves are empty.\r\n");
+    }
+    write!(conn, "The shelves hold:\r\n")?;
+    for (count, line) in util::stack_lines(shelved.iter().map(String::as_str)) {
+        write!(conn, "{}\r\n", util::stacked_line(count, line))?;
+    }
+    Ok(())
+}
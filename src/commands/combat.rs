@@ -0,0 +1,197 @@
+use crate::room::{Exit, RoomFlag, RoomId};
+use crate::skill;
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::Result as IoResult;
+
+use super::movement;
+
+pub fn kill(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let refusal = match util::take_argument(arguments).0 {
+        None => Err("Kill whom?"),
+        Some(_) if world.characters[char_idx].fighting().is_some() => {
+            Err("You do the best you can!")
+        }
+        Some(_) if world.rooms[&room_id].has_flag(RoomFlag::Safe) => Err("You can't fight here."),
+        Some(keyword) => match find_other(char_idx, room_id, keyword, world) {
+            Some(victim_idx) if world.characters[victim_idx].is_player() => {
+                Err("Challenge them to a duel instead.")
+            }
+            Some(victim_idx) => Ok(victim_idx),
+            None => Err("They aren't here."),
+        },
+    };
+    match refusal {
+        Ok(victim_idx) => {
+            world.start_fighting(char_idx, victim_idx);
+            world.attack(char_idx, victim_idx);
+        }
+        Err(message) => world.msg_char(message, Recipient::Subject(char_idx)),
+    }
+    Ok(())
+}
+
+pub fn fight(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    kill(conn_idx, room_id, arguments, world)
+}
+
+pub fn flee(conn_idx: Index, room_id: RoomId, _arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = &world.characters[char_idx];
+    if char.fighting().is_none() {
+        world.msg_char("You aren't fighting anyone.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let char_name = char.formal_name().to_string();
+
+    // Panic picks the way out, and it might not be open. Nobody flees through
+    // an exit they haven't found.
+    let exits: Vec<&Exit> = world.rooms[&room_id]
+        .exits
+        .0
+        .iter()
+        .filter(|exit| exit.is_visible())
+        .collect();
+    let exit = match exits.len() {
+        0 => None,
+        count => exits.get(util::random_range(0, count as u32 - 1) as usize),
+    }
+    .filter(|exit| {
+        world.rooms.contains_key(&exit.to)
+            && !exit.door.is_closed()
+            && !movement::is_crowded(exit.to, world)
+    });
+    let exit = match exit {
+        Some(exit) => exit,
+        None => {
+            world.msg_char("PANIC! You couldn't escape!", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let to_room = exit.to;
+    let leave_msg = format!("{} has fled {}!", char_name, exit.dir.leaving());
+    let arrive_msg = format!(
+        "{} arrives from {}, panting.",
+        char_name,
+        exit.dir.arriving()
+    );
+
+    // Running from a duel is as good as losing it.
+    if let Some(rival_idx) = world.characters[char_idx].duelling() {
+        world.end_duel(rival_idx, char_idx);
+    }
+    world.stop_fighting(char_idx, true);
+    world.msg_char("You flee from combat!", Recipient::Subject(char_idx));
+    movement::transfer(
+        conn_idx,
+        char_idx,
+        room_id,
+        to_room,
+        &leave_msg,
+        &arrive_msg,
+        world,
+    )
+}
+
+pub fn rescue(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let target_idx = match util::take_argument(arguments).0 {
+        Some(keyword) => find_other(char_idx, room_id, keyword, world),
+        None => {
+            world.msg_char("Rescue whom?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let target_idx = match target_idx {
+        Some(target_idx) => target_idx,
+        None => {
+            world.msg_char("They aren't here.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let target = &world.characters[target_idx];
+    let attacker_idx = world.room_chars[&room_id]
+        .iter()
+        .copied()
+        .find(|idx| *idx != char_idx && world.characters[*idx].fighting() == Some(target_idx));
+    let refusal = if !target.is_player() {
+        Some("They don't need your help!")
+    } else if target.duelling().is_some() {
+        Some("It's a fair duel. Stay out of it.")
+    } else if world.characters[char_idx].fighting() == Some(target_idx) {
+        Some("Too late.")
+    } else if attacker_idx.is_none() {
+        Some("Nobody is fighting them right now.")
+    } else {
+        None
+    };
+    if let Some(refusal) = refusal {
+        world.msg_char(refusal, Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let attacker_idx = attacker_idx.unwrap();
+    let char_name = world.characters[char_idx].formal_name().to_string();
+    let target_name = target.formal_name().to_string();
+    let target_connected = target.connection().is_some();
+
+    let rescue = skill::find("rescue").unwrap();
+    let char = &mut world.characters[char_idx];
+    char.add_lag(rescue.lag);
+    let succeeded = util::random_percent() <= char.proficiency(rescue.name);
+    if char.improve_skill(rescue.name, succeeded) {
+        world.msg_char(
+            "You have become better at rescue!",
+            Recipient::Subject(char_idx),
+        );
+    }
+    if !succeeded {
+        world.msg_char("You fail the rescue.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    world.msg_char(
+        &format!("You rescue {}!", target_name),
+        Recipient::Subject(char_idx),
+    );
+    if target_connected {
+        world.msg_char(
+            &format!("{} rescues you!", char_name),
+            Recipient::Subject(target_idx),
+        );
+    }
+    world.msg_char(
+        &format!("{} rescues {}!", char_name, target_name),
+        Recipient::Neither(char_idx, target_idx, room_id),
+    );
+    world.stop_fighting(attacker_idx, false);
+    world.stop_fighting(target_idx, false);
+    world.stop_fighting(char_idx, false);
+    world.start_fighting(char_idx, attacker_idx);
+    world.characters[attacker_idx].set_fighting(Some(char_idx));
+    Ok(())
+}
+
+// Someone else in the room, by keyword.
+pub(super) fn find_other(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+    world: &World,
+) -> Option<Index> {
+    let characters = &world.characters;
+    util::find_by_keyword(
+        world.room_chars[&room_id]
+            .iter()
+            .filter(|idx| **idx != char_idx && world.can_see_char(char_idx, **idx))
+            .filter_map(|idx| characters.get(*idx)),
+        keyword,
+    )
+    .and_then(|char| char.index())
+}
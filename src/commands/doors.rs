@@ -0,0 +1,225 @@
+use crate::character::Character;
+use crate::object::ObjectId;
+use crate::room::{Direction, Door, DoorError, RoomId};
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+
+// Until there are skills, everyone is equally mediocre at picking locks.
+const PICK_CHANCE: u32 = 50;
+
+#[derive(Clone, Copy)]
+enum DoorAction {
+    Open,
+    Close,
+    Lock,
+    Unlock,
+    Pick,
+}
+
+impl DoorAction {
+    fn verb(&self) -> &'static str {
+        match self {
+            DoorAction::Open => "open",
+            DoorAction::Close => "close",
+            DoorAction::Lock => "lock",
+            DoorAction::Unlock => "unlock",
+            DoorAction::Pick => "pick",
+        }
+    }
+
+    fn apply(&self, door: &Door) -> Result<Door, DoorError> {
+        match self {
+            DoorAction::Open => door.open(),
+            DoorAction::Close => door.close(),
+            DoorAction::Lock => door.lock(),
+            DoorAction::Unlock | DoorAction::Pick => door.unlock(),
+        }
+    }
+
+    fn error_message(&self, error: DoorError) -> &'static str {
+        match (self, error) {
+            (_, DoorError::NoDoor) => "There's no door there.",
+            (_, DoorError::NoLock) => "It doesn't have a lock.",
+            (DoorAction::Open, DoorError::Opened) => "It's already open.",
+            (_, DoorError::Opened) => "You'll have to close it first.",
+            (_, DoorError::Closed) => "It's already closed.",
+            (DoorAction::Open, DoorError::Locked) => "It's locked.",
+            (_, DoorError::Locked) => "It's already locked.",
+            (_, DoorError::Unlocked) => "It's already unlocked.",
+        }
+    }
+}
+
+pub fn open(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    door_command(conn_idx, room_id, arguments, DoorAction::Open, world)
+}
+
+pub fn close(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    door_command(conn_idx, room_id, arguments, DoorAction::Close, world)
+}
+
+pub fn lock(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    door_command(conn_idx, room_id, arguments, DoorAction::Lock, world)
+}
+
+pub fn unlock(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    door_command(conn_idx, room_id, arguments, DoorAction::Unlock, world)
+}
+
+pub fn pick(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    door_command(conn_idx, room_id, arguments, DoorAction::Pick, world)
+}
+
+fn door_command(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    action: DoorAction,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword.to_ascii_lowercase(),
+        (None, _) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "{} what?\r\n", util::capitalize(action.verb()));
+        }
+    };
+    // Directions always mean doors. Anything else might be a container.
+    let result = match Direction::parse(&keyword) {
+        None => try_container(char_idx, room_id, &keyword, action, world),
+        Some(_) => None,
+    };
+    let result = result.unwrap_or_else(|| try_door(char_idx, room_id, &keyword, action, world));
+    match result {
+        Ok((name, other_room)) => {
+            let char_name = world
+                .characters
+                .get(char_idx)
+                .expect("Unwrapped None character")
+                .formal_name()
+                .to_string();
+            let (you, they, other_side) = match action {
+                DoorAction::Open => ("You open", "opens", "opens."),
+                DoorAction::Close => ("You close", "closes", "closes."),
+                DoorAction::Lock => ("You lock", "locks", "clicks as it locks."),
+                DoorAction::Unlock => ("You unlock", "unlocks", "clicks as it unlocks."),
+                DoorAction::Pick => ("You pick the lock on", "picks the lock on", "clicks."),
+            };
+            world.msg_char(&format!("{} {}.", you, name), Recipient::Subject(char_idx));
+            world.msg_char(
+                &format!("{} {} {}.", char_name, they, name),
+                Recipient::NotSubject(char_idx, room_id),
+            );
+            if let Some(other_room) = other_room.filter(|to| world.room_chars.contains_key(to)) {
+                world.msg_char(
+                    &format!("{} {}", util::capitalize(&name), other_side),
+                    Recipient::All(other_room),
+                );
+            }
+            Ok(())
+        }
+        Err(message) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            write!(conn, "{}\r\n", message)
+        }
+    }
+}
+
+// Works out what a lid or door turns into, checking for keys and rolling for picks.
+fn change_lid(
+    action: DoorAction,
+    lid: Door,
+    key: Option<ObjectId>,
+    name: &str,
+    char: &Character,
+) -> Result<Door, String> {
+    match action {
+        DoorAction::Lock | DoorAction::Unlock | DoorAction::Pick if key.is_none() => {
+            return Err(format!("There's no keyhole in {}.", name));
+        }
+        DoorAction::Lock | DoorAction::Unlock if !key.is_some_and(|key| char.has_object(key)) => {
+            return Err(String::from("You don't have the key."));
+        }
+        _ => {}
+    }
+    let lid = action
+        .apply(&lid)
+        .map_err(|e| action.error_message(e).to_string())?;
+    if let DoorAction::Pick = action {
+        if util::random_percent() > PICK_CHANCE {
+            return Err(String::from("You failed."));
+        }
+    }
+    Ok(lid)
+}
+
+// Changes the lid of a container being carried or lying here. Returns None if
+// there's no such container, so the keyword can be tried as a door instead.
+fn try_container(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+    action: DoorAction,
+    world: &mut World,
+) -> Option<Result<(String, Option<RoomId>), String>> {
+    let char = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character");
+    let obj = util::find_item_by_keyword(&char.inventory, keyword)
+        .or_else(|| util::find_item_by_keyword(&world.room_objs[&room_id], keyword))?;
+    let container = obj.container()?;
+    if !container.lid.get().is_present() {
+        return Some(Err(format!("You can't {} {}.", action.verb(), obj.name())));
+    }
+    Some(
+        change_lid(action, container.lid.get(), container.key, obj.name(), char).map(|lid| {
+            container.lid.set(lid);
+            (obj.name().to_string(), None)
+        }),
+    )
+}
+
+// Changes the door on both sides. Returns its name and the room on the other side.
+fn try_door(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+    action: DoorAction,
+    world: &mut World,
+) -> Result<(String, Option<RoomId>), String> {
+    let room = world.rooms.get(&room_id).expect("Unwrapped None room");
+    let exit = room
+        .exits
+        .find_door(keyword)
+        .cloned()
+        .ok_or_else(|| format!("You don't see any {} here.", keyword))?;
+    let char = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character");
+    let door = change_lid(action, exit.door, exit.key, exit.door_name(), char)?;
+
+    let room = world.rooms.get_mut(&room_id).expect("Unwrapped None room");
+    if let Some(near) = room.exits.0.iter_mut().find(|near| near.dir == exit.dir) {
+        near.door = door;
+    }
+    if let Some(far) = world
+        .rooms
+        .get_mut(&exit.to)
+        .and_then(|other| other.exits.find_return(room_id, &exit.dir))
+    {
+        if far.door.is_present() {
+            far.door = door;
+        }
+    }
+    Ok((exit.door_name().to_string(), Some(exit.to)))
+}
@@ -0,0 +1,211 @@
+use crate::combat;
+use crate::room::{RoomFlag, RoomId};
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::Result as IoResult;
+
+use super::combat::find_other;
+
+pub fn challenge(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let rival_idx = match util::take_argument(arguments).0 {
+        None => Err("Challenge whom?"),
+        Some(_) if world.characters[char_idx].fighting().is_some() => {
+            Err("You're in no position to be challenging anyone.")
+        }
+        Some(_) if world.rooms[&room_id].has_flag(RoomFlag::Safe) => Err("You can't fight here."),
+        Some(keyword) => match find_other(char_idx, room_id, keyword, world) {
+            Some(rival_idx) if !world.characters[rival_idx].is_player() => {
+                Err("Only another player would take you up on that.")
+            }
+            Some(rival_idx) if world.characters[rival_idx].fighting().is_some() => {
+                Err("They're busy fighting already.")
+            }
+            Some(rival_idx) => Ok(rival_idx),
+            None => Err("They aren't here."),
+        },
+    };
+    let rival_idx = match rival_idx {
+        Ok(rival_idx) => rival_idx,
+        Err(message) => {
+            world.msg_char(message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = &mut world.characters[char_idx];
+    char.set_challenged(Some(rival_idx));
+    let char_name = char.formal_name().to_string();
+    let rival = &world.characters[rival_idx];
+    let rival_name = rival.formal_name().to_string();
+    let rival_connected = rival.connection().is_some();
+
+    world.msg_char(
+        &format!("You challenge {} to a duel.", rival_name),
+        Recipient::Subject(char_idx),
+    );
+    if rival_connected {
+        world.msg_char(
+            &format!(
+                "{} challenges you to a duel! Type 'accept {}' to take it up.",
+                char_name, char_name
+            ),
+            Recipient::Subject(rival_idx),
+        );
+    }
+    world.msg_char(
+        &format!("{} challenges {} to a duel!", char_name, rival_name),
+        Recipient::Neither(char_idx, rival_idx, room_id),
+    );
+    Ok(())
+}
+
+pub fn accept(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let keyword = util::take_argument(arguments).0;
+    let characters = &world.characters;
+    // Without a name, take up whichever challenge is waiting.
+    let rival_idx = match keyword {
+        Some(keyword) => find_other(char_idx, room_id, keyword, world)
+            .filter(|idx| characters[*idx].challenged() == Some(char_idx)),
+        None => world.room_chars[&room_id]
+            .iter()
+            .copied()
+            .find(|idx| characters[*idx].challenged() == Some(char_idx)),
+    };
+    let refusal = match rival_idx {
+        None => Some("Nobody here has challenged you."),
+        Some(_) if characters[char_idx].fighting().is_some() => {
+            Some("You're busy fighting already.")
+        }
+        Some(rival_idx) if characters[rival_idx].fighting().is_some() => {
+            Some("They're busy fighting already.")
+        }
+        Some(_) => None,
+    };
+    if let Some(refusal) = refusal {
+        world.msg_char(refusal, Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let rival_idx = rival_idx.unwrap();
+    let char_name = world.characters[char_idx].formal_name().to_string();
+    let rival = &mut world.characters[rival_idx];
+    rival.set_challenged(None);
+    rival.set_duelling(Some(char_idx));
+    let rival_name = rival.formal_name().to_string();
+    let rival_connected = rival.connection().is_some();
+    world.characters[char_idx].set_challenged(None);
+    world.characters[char_idx].set_duelling(Some(rival_idx));
+
+    world.msg_char(
+        &format!("You accept {}'s challenge. En garde!", rival_name),
+        Recipient::Subject(char_idx),
+    );
+    if rival_connected {
+        world.msg_char(
+            &format!("{} accepts your challenge. En garde!", char_name),
+            Recipient::Subject(rival_idx),
+        );
+    }
+    world.msg_char(
+        &format!(
+            "{} accepts {}'s challenge, and the duel begins!",
+            char_name, rival_name
+        ),
+        Recipient::Neither(char_idx, rival_idx, room_id),
+    );
+    world.start_fighting(rival_idx, char_idx);
+    Ok(())
+}
+
+pub fn yield_duel(
+    conn_idx: Index,
+    room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = &world.characters[char_idx];
+    let rival_idx = match char.duelling() {
+        Some(rival_idx) => rival_idx,
+        None => {
+            world.msg_char("You aren't in a duel.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char_name = char.formal_name().to_string();
+    let rival_name = world.characters[rival_idx].formal_name().to_string();
+    world.msg_char(
+        &format!("You yield to {}.", rival_name),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} yields to {}.", char_name, rival_name),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    world.end_duel(rival_idx, char_idx);
+    Ok(())
+}
+
+pub fn consider(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let rival_idx = match util::take_argument(arguments).0 {
+        Some(keyword) => find_other(char_idx, room_id, keyword, world),
+        None => {
+            world.msg_char("Consider whom?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let rival_idx = match rival_idx {
+        Some(rival_idx) => rival_idx,
+        None => {
+            world.msg_char("They aren't here.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let strength = |idx: Index| {
+        let char = &world.characters[idx];
+        combat::duel_strength(
+            char.vitals(),
+            &char.attributes(),
+            char.equipment.wielded().is_some(),
+        )
+    };
+    let rival = &world.characters[rival_idx];
+    let mut lines = vec![combat::assess(
+        strength(char_idx),
+        strength(rival_idx),
+        rival.formal_name(),
+    )];
+    if rival.is_player() {
+        let duels = world.connections[conn_idx].player().duels();
+        let tally = duels.against(rival.formal_name());
+        if tally.wins + tally.losses > 0 {
+            lines.push(format!(
+                "You've won {} and lost {} of your duels with {}.",
+                tally.wins,
+                tally.losses,
+                rival.formal_name()
+            ));
+        }
+    }
+    for line in lines {
+        world.msg_char(&line, Recipient::Subject(char_idx));
+    }
+    Ok(())
+}
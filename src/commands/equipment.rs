@@ -0,0 +1,223 @@
+use crate::object::{EquipSlot, Object, WearLocation};
+use crate::room::RoomId;
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum WearVerb {
+    Wear,
+    Wield,
+    Hold,
+}
+
+impl WearVerb {
+    // Wearing is the catch-all; wield and hold only take what they name.
+    fn allows(&self, location: WearLocation) -> bool {
+        match self {
+            WearVerb::Wear => true,
+            WearVerb::Wield => location == WearLocation::Wield,
+            WearVerb::Hold => location == WearLocation::Hold || location == WearLocation::Light,
+        }
+    }
+
+    fn refusal(&self) -> &'static str {
+        match self {
+            WearVerb::Wear => "You can't wear, wield, or hold that.",
+            WearVerb::Wield => "You can't wield that.",
+            WearVerb::Hold => "You can't hold that.",
+        }
+    }
+}
+
+pub fn wear(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    match util::take_argument(arguments) {
+        (Some("all"), _) => wear_all(conn_idx, room_id, world),
+        _ => wear_command(conn_idx, room_id, arguments, WearVerb::Wear, world),
+    }
+}
+
+pub fn wield(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    wear_command(conn_idx, room_id, arguments, WearVerb::Wield, world)
+}
+
+pub fn hold(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    wear_command(conn_idx, room_id, arguments, WearVerb::Hold, world)
+}
+
+fn wear_command(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    verb: WearVerb,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world
+        .characters
+        .get_mut(char_idx)
+        .expect("Unwrapped None character");
+    let result = match util::take_argument(arguments) {
+        (Some(keyword), _) => match util::pluck_item_from_list(&mut char.inventory, keyword) {
+            Some(obj) => match obj
+                .wear_location()
+                .filter(|location| verb.allows(*location))
+            {
+                Some(location) => Ok((obj, location)),
+                None => {
+                    char.inventory.push_front(obj);
+                    Err(verb.refusal())
+                }
+            },
+            None => Err("You aren't carrying that."),
+        },
+        (None, _) => Err(match verb {
+            WearVerb::Wear => "Wear what?",
+            WearVerb::Wield => "Wield what?",
+            WearVerb::Hold => "Hold what?",
+        }),
+    };
+    let (obj, location) = match result {
+        Ok(found) => found,
+        Err(message) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "{}\r\n", message);
+        }
+    };
+
+    // With every slot taken, the new object pushes out whatever is in the first.
+    let slot = char
+        .equipment
+        .free_slot(location)
+        .unwrap_or(location.slots()[0]);
+    if let Some(previous) = char.equipment.remove(slot) {
+        char.inventory.push_front(Rc::clone(&previous));
+        announce_removal(char_idx, room_id, &previous, world);
+    }
+    equip(char_idx, room_id, slot, obj, world);
+    Ok(())
+}
+
+fn wear_all(conn_idx: Index, room_id: RoomId, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world
+        .characters
+        .get_mut(char_idx)
+        .expect("Unwrapped None character");
+    let equipment = &char.equipment;
+    let mut worn = vec![];
+    let mut cursor = char.inventory.front_mut();
+    while let Some(obj) = cursor.get() {
+        // Anything that would push out what's already worn gets left alone.
+        let slot = obj
+            .wear_location()
+            .and_then(|location| equipment.free_slot(location))
+            .filter(|slot| worn.iter().all(|(taken, _)| taken != slot));
+        match slot {
+            Some(slot) => worn.push((slot, cursor.remove().unwrap())),
+            None => cursor.move_next(),
+        }
+    }
+    if worn.is_empty() {
+        let conn = world.connections.get_mut(conn_idx).unwrap();
+        return write!(conn, "You have nothing else to wear.\r\n");
+    }
+    for (slot, obj) in worn {
+        equip(char_idx, room_id, slot, obj, world);
+    }
+    Ok(())
+}
+
+fn equip(char_idx: Index, room_id: RoomId, slot: EquipSlot, obj: Rc<Object>, world: &mut World) {
+    let char = world
+        .characters
+        .get_mut(char_idx)
+        .expect("Unwrapped None character");
+    let name = char.formal_name().to_string();
+    let possessive = char.pronoun().possessive();
+    let (you, they) = match slot {
+        EquipSlot::Light => (
+            format!("You light {} and hold it.", obj.name()),
+            format!("{} lights {} and holds it.", name, obj.name()),
+        ),
+        EquipSlot::Wield => (
+            format!("You wield {}.", obj.name()),
+            format!("{} wields {}.", name, obj.name()),
+        ),
+        EquipSlot::Hold => (
+            format!("You hold {} {}.", obj.name(), slot.body_part("your")),
+            format!(
+                "{} holds {} {}.",
+                name,
+                obj.name(),
+                slot.body_part(possessive)
+            ),
+        ),
+        _ => (
+            format!("You wear {} {}.", obj.name(), slot.body_part("your")),
+            format!(
+                "{} wears {} {}.",
+                name,
+                obj.name(),
+                slot.body_part(possessive)
+            ),
+        ),
+    };
+    char.equipment.equip(slot, obj);
+    world.msg_char(&you, Recipient::Subject(char_idx));
+    world.msg_char(&they, Recipient::NotSubject(char_idx, room_id));
+}
+
+fn announce_removal(char_idx: Index, room_id: RoomId, obj: &Object, world: &mut World) {
+    let char = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character");
+    let name = char.formal_name().to_string();
+    world.msg_char(
+        &format!("You stop using {}.", obj.name()),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} stops using {}.", name, obj.name()),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+}
+
+pub fn remove(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world
+        .characters
+        .get_mut(char_idx)
+        .expect("Unwrapped None character");
+    let slots: Vec<EquipSlot> = match util::take_argument(arguments) {
+        (Some("all"), _) => char.equipment.iter().map(|(slot, _)| slot).collect(),
+        (Some(keyword), _) => char.equipment.find(keyword).into_iter().collect(),
+        (None, _) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "Remove what?\r\n");
+        }
+    };
+    if slots.is_empty() {
+        let conn = world.connections.get_mut(conn_idx).unwrap();
+        return write!(conn, "You aren't using that.\r\n");
+    }
+    for slot in slots {
+        let char = world
+            .characters
+            .get_mut(char_idx)
+            .expect("Unwrapped None character");
+        if let Some(obj) = char.equipment.remove(slot) {
+            char.inventory.push_front(Rc::clone(&obj));
+            announce_removal(char_idx, room_id, &obj, world);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use crate::character::{Effect, EffectKind};
+use crate::object::{Liquid, Object};
+use crate::room::RoomId;
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::Result as IoResult;
+
+fn char_name(char_idx: Index, world: &World) -> String {
+    world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string()
+}
+
+// Anything poisoned makes you sick for twice as long as it would have fed you.
+fn poison(char_idx: Index, hours: u32, world: &mut World) {
+    let char = world.characters.get_mut(char_idx).unwrap();
+    char.add_effect(Effect::new(EffectKind::Poison, (hours * 2).max(1)));
+    world.msg_char("You choke and gag.", Recipient::Subject(char_idx));
+}
+
+// Cures first, so that a potion can cure something and then protect against it.
+fn take_medicine(char_idx: Index, obj: &Object, world: &mut World) {
+    let medicine = match obj.medicine() {
+        Some(medicine) => medicine.clone(),
+        None => return,
+    };
+    let mut messages = vec![];
+    let char = world.characters.get_mut(char_idx).unwrap();
+    for kind in medicine.cures {
+        if char.remove_effect(kind) {
+            messages.push(kind.end_message());
+        }
+    }
+    for effect in medicine.effects {
+        char.add_effect(effect);
+        messages.push(effect.kind.start_message());
+    }
+    for message in messages {
+        world.msg_char(message, Recipient::Subject(char_idx));
+    }
+}
+
+pub fn eat(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Eat what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = world.characters.get_mut(char_idx).unwrap();
+    let obj = match util::find_item_by_keyword(&char.inventory, keyword) {
+        Some(obj) => obj,
+        None => {
+            world.msg_char("You aren't carrying that.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let refusal = match (obj.food(), obj.medicine()) {
+        (None, None) => Some("That's not edible."),
+        (Some(_), _) if char.is_player() && char.condition().is_stuffed() => {
+            Some("You are too full to eat more.")
+        }
+        _ => None,
+    };
+    if let Some(refusal) = refusal {
+        world.msg_char(refusal, Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    let obj = util::pluck_item_from_list(&mut char.inventory, keyword).unwrap();
+    let char_name = char_name(char_idx, world);
+    world.msg_char(
+        &format!("You eat {}.", obj.name()),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} eats {}.", char_name, obj.name()),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    if let Some(food) = obj.food() {
+        let char = world.characters.get_mut(char_idx).unwrap();
+        char.condition_mut().nourish(food.hours as i32, 0);
+        if char.condition().is_stuffed() {
+            world.msg_char("You are full.", Recipient::Subject(char_idx));
+        }
+        if food.poisoned {
+            poison(char_idx, food.hours, world);
+        }
+    }
+    take_medicine(char_idx, &obj, world);
+    world.extract_object(&obj);
+    Ok(())
+}
+
+pub fn quaff(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Quaff what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = world.characters.get_mut(char_idx).unwrap();
+    let obj = match util::pluck_item_from_list(&mut char.inventory, keyword) {
+        Some(obj) if obj.medicine().is_some() => obj,
+        Some(obj) => {
+            char.inventory.push_front(obj);
+            world.msg_char("You can't quaff that.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+        None => {
+            world.msg_char("You aren't carrying that.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    let char_name = char_name(char_idx, world);
+    world.msg_char(
+        &format!("You quaff {}.", obj.name()),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} quaffs {}.", char_name, obj.name()),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    take_medicine(char_idx, &obj, world);
+    world.extract_object(&obj);
+    Ok(())
+}
+
+// With no argument, drinks from whatever fountain is here.
+fn find_drink<'a>(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: Option<&str>,
+    world: &'a World,
+) -> Result<&'a Object, String> {
+    let room_objs = &world.room_objs[&room_id];
+    let keyword = match keyword {
+        Some(keyword) => keyword,
+        None => {
+            return room_objs
+                .iter()
+                .find(|obj| obj.drink().is_some_and(|drink| drink.fountain))
+                .ok_or_else(|| String::from("Drink what?"));
+        }
+    };
+    let char = world.characters.get(char_idx).unwrap();
+    let obj = util::find_item_by_keyword(&char.inventory, keyword)
+        .or_else(|| util::find_item_by_keyword(room_objs, keyword))
+        .ok_or_else(|| format!("You don't see any {} here.", keyword))?;
+    match obj.drink() {
+        None => Err(String::from("You can't drink from that.")),
+        Some(drink) if drink.is_empty() => {
+            Err(format!("{} is empty.", util::capitalize(obj.name())))
+        }
+        Some(_) => Ok(obj),
+    }
+}
+
+pub fn drink(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world.characters.get(char_idx).unwrap();
+    if char.is_player() && char.condition().is_sloshing() {
+        world.msg_char("You can't drink any more.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    let result =
+        find_drink(char_idx, room_id, util::take_argument(arguments).0, world).map(|obj| {
+            let drink = obj.drink().unwrap();
+            let poisoned = drink.poisoned.get();
+            let liquid = drink.sip();
+            (liquid, poisoned, obj.name().to_string())
+        });
+    let (liquid, poisoned, obj_name) = match result {
+        Ok(sipped) => sipped,
+        Err(message) => {
+            world.msg_char(&message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    let char_name = char_name(char_idx, world);
+    world.msg_char(
+        &format!("You drink {} from {}.", liquid.name(), obj_name),
+        Recipient::Subject(char_idx),
+    );
+    world.msg_char(
+        &format!("{} drinks {} from {}.", char_name, liquid.name(), obj_name),
+        Recipient::NotSubject(char_idx, room_id),
+    );
+    let char = world.characters.get_mut(char_idx).unwrap();
+    let (full, quenched) = liquid.nourishment();
+    char.condition_mut().nourish(full, quenched);
+    if char.condition().is_sloshing() {
+        world.msg_char("Your thirst is quenched.", Recipient::Subject(char_idx));
+    }
+    if poisoned {
+        poison(char_idx, 1, world);
+    }
+    Ok(())
+}
+
+pub fn fill(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let result = fill_from_fountain(char_idx, room_id, arguments, world);
+    match result {
+        Ok((liquid, obj_name, fountain_name)) => {
+            let char_name = char_name(char_idx, world);
+            world.msg_char(
+                &format!(
+                    "You fill {} with {} from {}.",
+                    obj_name,
+                    liquid.name(),
+                    fountain_name
+                ),
+                Recipient::Subject(char_idx),
+            );
+            world.msg_char(
+                &format!("{} fills {} from {}.", char_name, obj_name, fountain_name),
+                Recipient::NotSubject(char_idx, room_id),
+            );
+        }
+        Err(message) => world.msg_char(message, Recipient::Subject(char_idx)),
+    }
+    Ok(())
+}
+
+fn fill_from_fountain(
+    char_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    world: &World,
+) -> Result<(Liquid, String, String), &'static str> {
+    let keyword = util::take_argument(arguments).0.ok_or("Fill what?")?;
+    let char = world.characters.get(char_idx).unwrap();
+    let obj =
+        util::find_item_by_keyword(&char.inventory, keyword).ok_or("You aren't carrying that.")?;
+    let drink = obj
+        .drink()
+        .filter(|drink| !drink.fountain)
+        .ok_or("You can't fill that.")?;
+    let fountain = world.room_objs[&room_id]
+        .iter()
+        .find(|obj| obj.drink().is_some_and(|drink| drink.fountain))
+        .ok_or("There is no fountain here!")?;
+    let source = fountain.drink().unwrap();
+    if drink.amount.get() > 0 && drink.liquid.get() != source.liquid.get() {
+        return Err("There is already another liquid in it.");
+    }
+    if drink.amount.get() >= drink.capacity {
+        return Err("Your container is full.");
+    }
+    drink.liquid.set(source.liquid.get());
+    drink.amount.set(drink.capacity);
+    drink.poisoned.set(source.poisoned.get());
+    Ok((
+        source.liquid.get(),
+        obj.name().to_string(),
+        fountain.name().to_string(),
+    ))
+}
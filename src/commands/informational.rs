@@ -2,12 +2,20 @@ use crate::room::RoomId;
 use crate::util;
 use crate::world::World;
 use generational_arena::Index;
+use std::cmp::Reverse;
 use std::io::{Result as IoResult, Write};
 
 pub fn look(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
-    let (arg, _) = util::take_argument(arguments);
+    let (arg, rest) = util::take_argument(arguments);
     match arg {
         Some("auto") | None => util::look_room(conn_idx, room_id, world),
+        Some("in") => match util::take_argument(rest) {
+            (Some(target), _) => util::look_in(conn_idx, room_id, target, world),
+            (None, _) => {
+                let conn = world.connections.get_mut(conn_idx).unwrap();
+                write!(conn, "Look in what?\r\n")
+            }
+        },
         Some(target) => util::look_at(conn_idx, room_id, target, world),
     }
 }
@@ -27,8 +35,209 @@ pub fn inventory(
         .get(conn.character)
         .expect("Unwrapped None character");
     write!(conn, "You are carrying:\r\n")?;
-    for obj in &char.inventory {
-        write!(conn, "    {}\r\n", obj.name())?;
+    for (count, line) in util::stack_lines(char.inventory.iter().map(|obj| obj.name())) {
+        write!(conn, "{}\r\n", util::stacked_line(count, line))?;
+    }
+    write!(conn, "You have {} gold.\r\n", char.gold())
+}
+
+pub fn score(
+    conn_idx: Index,
+    _at_room: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let char = world
+        .characters
+        .get(conn.character)
+        .expect("Unwrapped None character");
+    let vitals = char.vitals();
+    let attributes = char.attributes();
+    let experience = char.experience();
+    let title = conn.player().title(experience.level).to_string();
+    write!(conn, "You are {} {}.\r\n", char.formal_name(), title)?;
+    write!(
+        conn,
+        "You are level {}, with {} experience points.",
+        experience.level, experience.exp
+    )?;
+    match experience.exp_to_level() {
+        Some(needed) => write!(conn, " You need {} more to level.\r\n", needed)?,
+        None => write!(conn, "\r\n")?,
+    }
+    write!(
+        conn,
+        "You have {}/{} hit points, {}/{} mana, and {}/{} movement.\r\n",
+        vitals.hp, vitals.max_hp, vitals.mana, vitals.max_mana, vitals.moves, vitals.max_moves
+    )?;
+    write!(
+        conn,
+        "Str: {}  Int: {}  Wis: {}  Dex: {}  Con: {}\r\n",
+        attributes.strength,
+        attributes.intelligence,
+        attributes.wisdom,
+        attributes.dexterity,
+        attributes.constitution
+    )?;
+    write!(
+        conn,
+        "You are carrying {} items weighing {} pounds, and have {} gold.\r\n",
+        char.carried_count(),
+        char.carried_weight(),
+        char.gold()
+    )?;
+    write!(
+        conn,
+        "You have {} practice sessions left.\r\n",
+        char.practices()
+    )?;
+    let duels = conn.player().duels().total();
+    if duels.wins + duels.losses > 0 {
+        write!(
+            conn,
+            "You have won {} duels and lost {}.\r\n",
+            duels.wins, duels.losses
+        )?;
+    }
+    write!(conn, "You are {}.\r\n", char.position().verb())?;
+    let condition = char.condition();
+    if condition.full == 0 {
+        write!(conn, "You are hungry.\r\n")?;
+    }
+    if condition.quenched == 0 {
+        write!(conn, "You are thirsty.\r\n")?;
     }
     Ok(())
 }
+
+pub fn who(conn_idx: Index, _at_room: RoomId, _arguments: &str, world: &mut World) -> IoResult<()> {
+    let viewer_idx = world.connections.get(conn_idx).unwrap().character;
+    let mut lines = vec![];
+    for (_, conn) in &world.connections {
+        let char = match world.characters.get(conn.character) {
+            Some(char) if world.can_see_char(viewer_idx, conn.character) => char,
+            _ => continue,
+        };
+        let admin = if conn.player().is_admin() {
+            " (Admin)"
+        } else {
+            ""
+        };
+        lines.push((
+            char.level(),
+            format!(
+                "[{:>2}] {} {}{}\r\n",
+                char.level(),
+                char.formal_name(),
+                conn.player().title(char.level()),
+                admin
+            ),
+        ));
+    }
+    // Highest levels first.
+    lines.sort_by_key(|(level, _)| Reverse(*level));
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    for (_, line) in &lines {
+        write!(conn, "{}", line)?;
+    }
+    write!(conn, "\r\nPlayers found: {}\r\n", lines.len())
+}
+
+pub fn equipment(
+    conn_idx: Index,
+    _at_room: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let char = world
+        .characters
+        .get(conn.character)
+        .expect("Unwrapped None character");
+    write!(conn, "You are using:\r\n")?;
+    if char.equipment.is_empty() {
+        write!(conn, "    Nothing.\r\n")
+    } else {
+        write!(conn, "{}", char.equipment)
+    }
+}
+
+pub fn exits(
+    conn_idx: Index,
+    room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let room = world.rooms.get(&room_id).expect("Unwrapped None room");
+    let mut lines = vec![];
+    for exit in room.exits.as_ref().iter().filter(|exit| exit.is_visible()) {
+        let destination = match world.rooms.get(&exit.to) {
+            Some(_) if !world.can_see_room(char_idx, exit.to) => "Too dark to tell",
+            Some(to) => to.name.as_str(),
+            None => "Somewhere else",
+        };
+        let mut line = format!("    {:<8} - {}", exit.dir.leaving(), destination);
+        if exit.door.is_present() {
+            let state = if exit.door.is_locked() {
+                "closed and locked"
+            } else if exit.door.is_closed() {
+                "closed"
+            } else {
+                "open"
+            };
+            line.push_str(&format!(" ({} is {})", exit.door_name(), state));
+        }
+        lines.push(line);
+    }
+
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    if lines.is_empty() {
+        return write!(conn, "There are no obvious exits.\r\n");
+    }
+    write!(conn, "Obvious exits:\r\n")?;
+    for line in lines {
+        write!(conn, "{}\r\n", line)?;
+    }
+    Ok(())
+}
+
+pub fn time(
+    conn_idx: Index,
+    _room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    write!(conn, "{}\r\n", world.time)
+}
+
+pub fn weather(
+    conn_idx: Index,
+    room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let room = world.rooms.get(&room_id).expect("Unwrapped None room");
+    if !room.is_outdoors() {
+        return write!(conn, "You can't see the weather indoors.\r\n");
+    }
+    write!(conn, "{}\r\n", world.areas[room.area].weather.describe())
+}
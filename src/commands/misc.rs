@@ -1,4 +1,4 @@
-use crate::character::PlayerRecord;
+use crate::character::{PlayerRecord, DEFAULT_PROMPT};
 use crate::room::RoomId;
 use crate::util;
 use crate::world::{Recipient, World};
@@ -20,8 +20,9 @@ pub fn save(
         .get(conn.character)
         .expect("Unwrapped None character")
         .clone();
-    let player_record = PlayerRecord::from_player(conn.player(), character);
-    match util::save(conn.player_name(), player_record) {
+    let saved = PlayerRecord::from_player(conn.player(), character)
+        .and_then(|player_record| util::save(conn.player_name(), player_record));
+    match saved {
         Ok(()) => write!(conn, "Saved!\r\n"),
         Err(_) => write!(conn, "Your character couldn't be saved.\r\n"),
     }
@@ -33,11 +34,20 @@ pub fn quit(
     _arguments: &str,
     world: &mut World,
 ) -> IoResult<()> {
+    let fighting = world
+        .connections
+        .get(conn_idx)
+        .and_then(|conn| world.characters.get(conn.character))
+        .is_some_and(|char| char.fighting().is_some());
+    if fighting {
+        let conn = world.connections.get_mut(conn_idx).unwrap();
+        return write!(conn, "No way! You are fighting.\r\n");
+    }
     let mut conn = world
         .connections
         .remove(conn_idx)
         .expect("Unwrapped None connection");
-    let character = world
+    let mut character = world
         .characters
         .remove(conn.character)
         .expect("Unwrapped None character");
@@ -45,8 +55,16 @@ pub fn quit(
     let formal_name = character.formal_name().to_string();
     let pronoun = character.pronoun();
 
-    let player_record = PlayerRecord::from_player(conn.player(), &character);
-    match util::save(conn.player_name(), player_record) {
+    let saved = PlayerRecord::from_player(conn.player(), &character)
+        .and_then(|player_record| util::save(conn.player_name(), player_record));
+    // Their things leave the world with them, and come back as new copies next login.
+    while let Some(obj) = character.inventory.pop_front() {
+        world.extract_object(&obj);
+    }
+    for obj in character.equipment.take_all() {
+        world.extract_object(&obj);
+    }
+    match saved {
         Ok(()) => {
             let _ = write!(conn, "Saved!\r\nGoodbye.\r\n");
             let _ = conn.write_flush(None);
@@ -71,3 +89,98 @@ pub fn quit(
         ),
     }
 }
+
+pub fn autoexits(
+    conn_idx: Index,
+    _room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let preferences = conn.player_mut().preferences_mut();
+    preferences.auto_exits = !preferences.auto_exits;
+    if preferences.auto_exits {
+        write!(conn, "Exits will be shown when you look around.\r\n")
+    } else {
+        write!(
+            conn,
+            "Exits will no longer be shown when you look around.\r\n"
+        )
+    }
+}
+
+pub fn prompt(
+    conn_idx: Index,
+    _room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let format = arguments.trim();
+    if format.is_empty() {
+        let current = conn.player().preferences().prompt.clone();
+        return write!(
+            conn,
+            "Your prompt is: {}\r\n\
+             Use %h, %m, and %v for hit points, mana, and movement, capitalized for \
+             their maximums, and %g for gold. 'prompt default' puts it back.\r\n",
+            current
+        );
+    }
+    let format = if format.eq_ignore_ascii_case("default") {
+        String::from(DEFAULT_PROMPT)
+    } else {
+        // A trailing space keeps what's typed apart from the prompt.
+        format!("{} ", format)
+    };
+    conn.player_mut().preferences_mut().prompt = format;
+    write!(conn, "Prompt set.\r\n")
+}
+
+// Same as Merc.
+const MAX_TITLE_LENGTH: usize = 45;
+
+pub fn title(
+    conn_idx: Index,
+    _room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world
+        .connections
+        .get_mut(conn_idx)
+        .expect("Unwrapped None connection");
+    let level = world
+        .characters
+        .get(conn.character)
+        .expect("Unwrapped None character")
+        .level();
+    let title = arguments.trim();
+    if title.is_empty() {
+        let current = conn.player().title(level).to_string();
+        return write!(
+            conn,
+            "Your title is: {}\r\n'title default' gives you the title for your level.\r\n",
+            current
+        );
+    }
+    if title.eq_ignore_ascii_case("default") {
+        conn.player_mut().set_title(None);
+        return write!(conn, "You'll be known by your level.\r\n");
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return write!(
+            conn,
+            "Titles can be at most {} letters long.\r\n",
+            MAX_TITLE_LENGTH
+        );
+    }
+    conn.player_mut().set_title(Some(title.to_string()));
+    write!(conn, "Title set.\r\n")
+}
@@ -1,5 +1,5 @@
 use super::informational::look;
-use crate::room::RoomId;
+use crate::room::{RoomFlag, RoomId};
 use crate::util;
 use crate::world::{Recipient, World};
 use generational_arena::Index;
@@ -50,6 +50,45 @@ pub fn go(conn_idx: Index, at_room: RoomId, arguments: &str, world: &mut World)
     }
 }
 
+pub fn search(
+    conn_idx: Index,
+    at_room: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char_name = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string();
+    let room = world.rooms.get_mut(&at_room).expect("Unwrapped None room");
+    let mut found = vec![];
+    for exit in room.exits.0.iter_mut().filter(|exit| !exit.is_visible()) {
+        exit.revealed = true;
+        found.push(exit.dir.leaving().to_string());
+    }
+
+    world.msg_char(
+        &format!("{} searches the room carefully.", char_name),
+        Recipient::NotSubject(char_idx, at_room),
+    );
+    if found.is_empty() {
+        world.msg_char(
+            "You search, but find nothing.",
+            Recipient::Subject(char_idx),
+        );
+    }
+    for dir in found {
+        world.msg_char(
+            &format!("You find a hidden exit {}!", dir),
+            Recipient::Subject(char_idx),
+        );
+    }
+    Ok(())
+}
+
 fn move_char(conn_idx: Index, at_room: RoomId, direction: &str, world: &mut World) -> IoResult<()> {
     let conn = world
         .connections
@@ -63,26 +102,200 @@ fn move_char(conn_idx: Index, at_room: RoomId, direction: &str, world: &mut Worl
         .formal_name()
         .to_string();
 
-    if let Some(exit) = world
-        .rooms
+    // An exit to a room that's gone leads nowhere.
+    let rooms = &world.rooms;
+    if let Some((exit, to_room)) = rooms
         .get(&from_room)
         .and_then(|room| room.exits.get(direction))
+        .filter(|exit| exit.is_visible())
+        .and_then(|exit| Some((exit, rooms.get(&exit.to)?)))
     {
-        let to_room = world.rooms.get(&exit.to).expect("Unwrapped None room").id;
         let char_idx = conn.character;
 
-        let leave_msg = format!("{} leaves {}.", char_name, exit.dir.leaving());
-        let arrive_msg = format!("{} arrives from {}.", char_name, exit.dir.arriving());
+        if exit.door.is_closed() {
+            let message = format!("{} is closed.", util::capitalize(exit.door_name()));
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "{}\r\n", message);
+        }
+
+        if is_crowded(to_room.id, world) {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "That room is private right now.\r\n");
+        }
 
-        world.char_from_room(char_idx, from_room);
-        world.msg_char(&leave_msg, Recipient::NotSubject(char_idx, from_room));
-        world.msg_char(&arrive_msg, Recipient::NotSubject(char_idx, to_room));
-        world.char_to_room(char_idx, to_room);
+        let from_sector = world
+            .rooms
+            .get(&from_room)
+            .expect("Unwrapped None room")
+            .sector;
+        // Same as Merc, the average of the two rooms' costs.
+        let cost = (from_sector.movement_cost() + to_room.sector.movement_cost()) / 2;
+        let char = world.characters.get_mut(char_idx).unwrap();
+        if !char.vitals_mut().spend_moves(cost) {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "You are too exhausted.\r\n");
+        }
 
-        util::look_room(conn_idx, to_room, world)?;
+        let leave_msg = format!(
+            "{} {} {}.",
+            char_name,
+            from_sector.leaving_verb(),
+            exit.dir.leaving()
+        );
+        let arrive_msg = format!(
+            "{} {} from {}.",
+            char_name,
+            to_room.sector.arriving_verb(),
+            exit.dir.arriving()
+        );
+        let to_room = to_room.id;
+        transfer(
+            conn_idx,
+            char_idx,
+            from_room,
+            to_room,
+            &leave_msg,
+            &arrive_msg,
+            world,
+        )?;
     } else {
         let conn = world.connections.get_mut(conn_idx).unwrap();
         write!(conn, "You can't go that way.\r\n")?;
     }
     Ok(())
 }
+
+// Prays to be taken back to the starting room, from anywhere that allows it.
+pub fn recall(
+    conn_idx: Index,
+    at_room: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char_name = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string();
+    let to_room = RoomId::default();
+
+    world.msg_char(
+        &format!("{} prays for transportation!", char_name),
+        Recipient::NotSubject(char_idx, at_room),
+    );
+    if at_room == to_room {
+        world.msg_char("You're already there.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+    if world
+        .rooms
+        .get(&at_room)
+        .is_some_and(|room| room.has_flag(RoomFlag::NoRecall))
+    {
+        world.msg_char("The gods have forsaken you.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    let leave_msg = format!("{} disappears.", char_name);
+    let arrive_msg = format!("{} appears in the room.", char_name);
+    transfer(
+        conn_idx,
+        char_idx,
+        at_room,
+        to_room,
+        &leave_msg,
+        &arrive_msg,
+        world,
+    )
+}
+
+// Private rooms only have space for two.
+pub(super) fn is_crowded(room_id: RoomId, world: &World) -> bool {
+    world
+        .rooms
+        .get(&room_id)
+        .is_some_and(|room| room.has_flag(RoomFlag::Private))
+        && world
+            .room_chars
+            .get(&room_id)
+            .map_or(0, |chars| chars.len())
+            >= 2
+}
+
+// Moves a character between rooms, letting both rooms know, and shows them where
+// they ended up.
+pub(super) fn transfer(
+    conn_idx: Index,
+    char_idx: Index,
+    from_room: RoomId,
+    to_room: RoomId,
+    leave_msg: &str,
+    arrive_msg: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    world.char_from_room(char_idx, from_room);
+    world.msg_char(leave_msg, Recipient::NotSubject(char_idx, from_room));
+    world.msg_char(arrive_msg, Recipient::NotSubject(char_idx, to_room));
+    world.char_to_room(char_idx, to_room);
+
+    util::look_room(conn_idx, to_room, world)?;
+    world.explore(char_idx, to_room);
+    Ok(())
+}
+
+pub fn enter(conn_idx: Index, at_room: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let result = util::take_argument(arguments)
+        .0
+        .ok_or("Enter what?")
+        .and_then(|keyword| {
+            util::find_item_by_keyword(&world.room_objs[&at_room], keyword)
+                .ok_or("You don't see that here.")
+        })
+        .and_then(|obj| {
+            let portal = obj.portal().ok_or("You can't enter that.")?;
+            let to_room = world
+                .portal_destination(portal)
+                .ok_or("It doesn't seem to lead anywhere.")?;
+            if is_crowded(to_room, world) {
+                return Err("That room is private right now.");
+            }
+            let used_up = portal.spend_charge();
+            Ok((obj.instance(), obj.name().to_string(), to_room, used_up))
+        });
+    let (portal, portal_name, to_room, used_up) = match result {
+        Ok(found) => found,
+        Err(message) => {
+            world.msg_char(message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    let char_name = world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string();
+    let leave_msg = format!("{} steps into {}.", char_name, portal_name);
+    let arrive_msg = format!("{} steps out of {}.", char_name, portal_name);
+    world.msg_char(
+        &format!("You step into {}.", portal_name),
+        Recipient::Subject(char_idx),
+    );
+    transfer(
+        conn_idx,
+        char_idx,
+        at_room,
+        to_room,
+        &leave_msg,
+        &arrive_msg,
+        world,
+    )?;
+    if used_up {
+        world.fade_portal(at_room, portal);
+    }
+    Ok(())
+}
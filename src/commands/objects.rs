@@ -1,3 +1,4 @@
+use crate::character::Character;
 use crate::object::Object;
 use crate::world::{Recipient, World};
 use crate::{util, ObjectInRoomAdapter, ObjectOnCharAdapter, RoomId};
@@ -9,30 +10,78 @@ use std::rc::Rc;
 fn try_get(
     room_objs: &mut LinkedList<ObjectInRoomAdapter>,
     arguments: &str,
-) -> Result<Rc<Object>, &'static str> {
+) -> Result<Vec<Rc<Object>>, &'static str> {
     let (keyword, _) = util::take_argument(arguments);
     let keyword = keyword.ok_or("Get what?\r\n")?;
-    util::pluck_item_from_list(room_objs, keyword).ok_or("That isn't here.\r\n")
+    Some(util::pluck_items_from_list(room_objs, keyword))
+        .filter(|objs| !objs.is_empty())
+        .ok_or("That isn't here.\r\n")
+}
+
+// Splits what's being picked up into what the character can manage and what's
+// too much, along with the reason for the first thing left behind.
+fn within_carry_limits(
+    char: &Character,
+    objs: Vec<Rc<Object>>,
+    already_weighed: bool,
+) -> (Vec<Rc<Object>>, Vec<Rc<Object>>, Option<String>) {
+    let mut taken = vec![];
+    let mut left = vec![];
+    let mut refusal = None;
+    for obj in objs {
+        let allowed = if already_weighed {
+            char.has_hands_for(&obj, &taken)
+        } else {
+            char.can_carry(&obj, &taken)
+        };
+        match allowed {
+            Ok(()) => taken.push(obj),
+            Err(message) => {
+                refusal.get_or_insert(message);
+                left.push(obj);
+            }
+        }
+    }
+    (taken, left, refusal)
 }
 
 pub fn get(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
     let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (object_keyword, rest) = util::take_argument(arguments);
+    let container_keyword = match util::take_argument(rest) {
+        (Some("from"), rest) => util::take_argument(rest).0,
+        (keyword, _) => keyword,
+    };
+    if let (Some(object_keyword), Some(container_keyword)) = (object_keyword, container_keyword) {
+        return get_from(char_idx, room_id, object_keyword, container_keyword, world);
+    }
+
     let room_objs = world.room_objs.get_mut(&room_id).unwrap();
     match try_get(room_objs, arguments) {
-        Ok(obj) => {
+        Ok(objs) => {
             // OOF!
             let char = world.characters.get(char_idx).unwrap();
             let char_name = char.formal_name().to_string();
-            world.msg_char(
-                &format!("You get {}.", obj.name()),
-                Recipient::Subject(char_idx),
-            );
-            world.msg_char(
-                &format!("{} gets {}.", char_name, obj.name()),
-                Recipient::NotSubject(char_idx, room_id),
-            );
-            let char = world.characters.get_mut(char_idx).unwrap();
-            char.inventory.push_front(obj);
+            let (objs, left, refusal) = within_carry_limits(char, objs, false);
+            let room_objs = world.room_objs.get_mut(&room_id).unwrap();
+            for obj in left {
+                room_objs.push_front(obj);
+            }
+            if let Some(refusal) = refusal {
+                world.msg_char(&refusal, Recipient::Subject(char_idx));
+            }
+            for obj in objs {
+                world.msg_char(
+                    &format!("You get {}.", obj.name()),
+                    Recipient::Subject(char_idx),
+                );
+                world.msg_char(
+                    &format!("{} gets {}.", char_name, obj.name()),
+                    Recipient::NotSubject(char_idx, room_id),
+                );
+                let char = world.characters.get_mut(char_idx).unwrap();
+                char.inventory.push_front(obj);
+            }
         }
         Err(e) => {
             let conn = world.connections.get_mut(conn_idx).unwrap();
@@ -51,10 +100,12 @@ pub fn take(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World
 fn try_drop(
     inv: &mut LinkedList<ObjectOnCharAdapter>,
     arguments: &str,
-) -> Result<Rc<Object>, &'static str> {
+) -> Result<Vec<Rc<Object>>, &'static str> {
     let (keyword, _) = util::take_argument(arguments);
     let keyword = keyword.ok_or("Drop what?\r\n")?;
-    util::pluck_item_from_list(inv, keyword).ok_or("You aren't carrying that.\r\n")
+    Some(util::pluck_items_from_list(inv, keyword))
+        .filter(|objs| !objs.is_empty())
+        .ok_or("You aren't carrying that.\r\n")
 }
 
 pub fn drop(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
@@ -62,18 +113,20 @@ pub fn drop(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World
     let char = world.characters.get_mut(char_idx).unwrap();
 
     match try_drop(&mut char.inventory, arguments) {
-        Ok(obj) => {
+        Ok(objs) => {
             let char_name = char.formal_name().to_string();
-            world.msg_char(
-                &format!("You drop {}.", obj.name()),
-                Recipient::Subject(char_idx),
-            );
-            world.msg_char(
-                &format!("{} drops {}.", char_name, obj.name()),
-                Recipient::NotSubject(char_idx, room_id),
-            );
-            let room_objs = world.room_objs.get_mut(&room_id).unwrap();
-            room_objs.push_front(obj);
+            for obj in objs {
+                world.msg_char(
+                    &format!("You drop {}.", obj.name()),
+                    Recipient::Subject(char_idx),
+                );
+                world.msg_char(
+                    &format!("{} drops {}.", char_name, obj.name()),
+                    Recipient::NotSubject(char_idx, room_id),
+                );
+                let room_objs = world.room_objs.get_mut(&room_id).unwrap();
+                room_objs.push_front(obj);
+            }
         }
         Err(e) => {
             let conn = world.connections.get_mut(conn_idx).unwrap();
@@ -104,10 +157,13 @@ pub fn give(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World
         }
     };
 
-    let target_idx = room_chars.iter()
-        .filter_map(|idx| world.characters.get(*idx))
-        .find(|char| char.keywords().iter().any(|kw| kw.starts_with(target_keyword)))
-        .and_then(|char| char.index());
+    let target_idx = util::find_by_keyword(
+        room_chars
+            .iter()
+            .filter_map(|idx| world.characters.get(*idx)),
+        target_keyword,
+    )
+    .and_then(|char| char.index());
 
     let target_idx = match target_idx {
         Some(idx) => idx,
@@ -117,22 +173,194 @@ pub fn give(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World
         }
     };
 
-    if let Some(obj) = util::pluck_item_from_list(&mut world.characters.get_mut(char_idx).unwrap().inventory, object_keyword) {
-        let source_char_name = world.characters.get(char_idx).unwrap().formal_name().to_string();
-        let target_char = world.characters.get_mut(target_idx).unwrap();
+    let objs = util::pluck_items_from_list(
+        &mut world.characters.get_mut(char_idx).unwrap().inventory,
+        object_keyword,
+    );
+    if !objs.is_empty() {
+        for obj in objs {
+            let source_char_name = world.characters.get(char_idx).unwrap().formal_name().to_string();
+            let target_char = world.characters.get_mut(target_idx).unwrap();
+            if target_char.can_carry(&obj, &[]).is_err() {
+                let message = format!("{} can't carry {}.", target_char.formal_name(), obj.name());
+                world
+                    .characters
+                    .get_mut(char_idx)
+                    .unwrap()
+                    .inventory
+                    .push_front(obj);
+                world.msg_char(&message, Recipient::Subject(char_idx));
+                continue;
+            }
 
-        let char_message = format!("You give {} to {}.", obj.name(), target_char.formal_name());
-        let target_message = format!("{} gives you {}.", source_char_name, obj.name());
-        let room_message = format!("{} gives {} {}.", source_char_name, target_char.formal_name(), obj.name());
+            let char_message = format!("You give {} to {}.", obj.name(), target_char.formal_name());
+            let target_message = format!("{} gives you {}.", source_char_name, obj.name());
+            let room_message = format!("{} gives {} {}.", source_char_name, target_char.formal_name(), obj.name());
 
-        target_char.inventory.push_front(obj);
+            target_char.inventory.push_front(obj);
 
-        world.msg_char(&char_message, Recipient::Subject(char_idx));
-        world.msg_char(&target_message, Recipient::Subject(target_idx));
-        world.msg_char(&room_message, Recipient::Neither(char_idx, target_idx, room_id));
+            world.msg_char(&char_message, Recipient::Subject(char_idx));
+            world.msg_char(&target_message, Recipient::Subject(target_idx));
+            world.msg_char(&room_message, Recipient::Neither(char_idx, target_idx, room_id));
+        }
     } else {
         world.msg_char(&format!("You aren't holding any {} in your inventory.", object_keyword), Recipient::Subject(char_idx));
     }
 
     Ok(())
 }
+
+// Finds a container that's carried or lying here, as long as it's open.
+fn open_container<'a>(
+    world: &'a World,
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+) -> Result<&'a Object, String> {
+    let char = world.characters.get(char_idx).unwrap();
+    let container = util::find_item_by_keyword(&char.inventory, keyword)
+        .or_else(|| util::find_item_by_keyword(&world.room_objs[&room_id], keyword))
+        .ok_or_else(|| format!("You don't see any {} here.", keyword))?;
+    match container.container() {
+        None => Err(format!(
+            "{} isn't a container.",
+            util::capitalize(container.name())
+        )),
+        Some(lid) if lid.is_closed() => {
+            Err(format!("{} is closed.", util::capitalize(container.name())))
+        }
+        Some(_) => Ok(container),
+    }
+}
+
+fn get_from(
+    char_idx: Index,
+    room_id: RoomId,
+    object_keyword: &str,
+    container_keyword: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let result =
+        open_container(world, char_idx, room_id, container_keyword).and_then(|container| {
+            let mut contents = container.contents.borrow_mut();
+            let objs = util::pluck_items_from_list(&mut contents, object_keyword);
+            if objs.is_empty() {
+                return Err(format!(
+                    "There's no {} in {}.",
+                    object_keyword,
+                    container.name()
+                ));
+            }
+            // What's in a bag the character is carrying already counts toward their weight.
+            let char = world.characters.get(char_idx).unwrap();
+            let carried = char
+                .inventory
+                .iter()
+                .any(|obj| std::ptr::eq(obj, container));
+            let (objs, left, refusal) = within_carry_limits(char, objs, carried);
+            for obj in left {
+                contents.push_front(obj);
+            }
+            Ok((objs, refusal, container.name().to_string()))
+        });
+    let (objs, refusal, container_name) = match result {
+        Ok(found) => found,
+        Err(message) => {
+            world.msg_char(&message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    if let Some(refusal) = refusal {
+        world.msg_char(&refusal, Recipient::Subject(char_idx));
+    }
+    let char_name = world
+        .characters
+        .get(char_idx)
+        .unwrap()
+        .formal_name()
+        .to_string();
+    for obj in objs {
+        world.msg_char(
+            &format!("You get {} from {}.", obj.name(), container_name),
+            Recipient::Subject(char_idx),
+        );
+        world.msg_char(
+            &format!("{} gets {} from {}.", char_name, obj.name(), container_name),
+            Recipient::NotSubject(char_idx, room_id),
+        );
+        world
+            .characters
+            .get_mut(char_idx)
+            .unwrap()
+            .inventory
+            .push_front(obj);
+    }
+    Ok(())
+}
+
+pub fn put(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (object_keyword, rest) = util::take_argument(arguments);
+    let container_keyword = match util::take_argument(rest) {
+        (Some("in"), rest) | (Some("into"), rest) => util::take_argument(rest).0,
+        (keyword, _) => keyword,
+    };
+    let (object_keyword, container_keyword) = match (object_keyword, container_keyword) {
+        (Some(object_keyword), Some(container_keyword)) => (object_keyword, container_keyword),
+        _ => {
+            world.msg_char("Put what in what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+
+    // Take the object out first, so that it can't end up inside itself.
+    let inventory = &mut world.characters.get_mut(char_idx).unwrap().inventory;
+    let obj = match util::pluck_item_from_list(inventory, object_keyword) {
+        Some(obj) => obj,
+        None => {
+            world.msg_char(
+                &format!("You aren't carrying any {}.", object_keyword),
+                Recipient::Subject(char_idx),
+            );
+            return Ok(());
+        }
+    };
+    let result =
+        open_container(world, char_idx, room_id, container_keyword).and_then(|container| {
+            let capacity = container.container().map_or(0, |c| c.capacity);
+            if container.contents.borrow().iter().count() >= capacity {
+                return Err(format!("{} is full.", util::capitalize(container.name())));
+            }
+            container.contents.borrow_mut().push_front(Rc::clone(&obj));
+            Ok(container.name().to_string())
+        });
+    match result {
+        Ok(container_name) => {
+            let char_name = world
+                .characters
+                .get(char_idx)
+                .unwrap()
+                .formal_name()
+                .to_string();
+            world.msg_char(
+                &format!("You put {} in {}.", obj.name(), container_name),
+                Recipient::Subject(char_idx),
+            );
+            world.msg_char(
+                &format!("{} puts {} in {}.", char_name, obj.name(), container_name),
+                Recipient::NotSubject(char_idx, room_id),
+            );
+        }
+        Err(message) => {
+            world
+                .characters
+                .get_mut(char_idx)
+                .unwrap()
+                .inventory
+                .push_front(obj);
+            world.msg_char(&message, Recipient::Subject(char_idx));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,1115 @@
+use crate::character::{
+    Attributes, CharId, CharacterData, Effect, EffectKind, Position, Pronoun, Shop, MAX_LEVEL,
+};
+use crate::object::{
+    Book, Container, Drink, Food, Furniture, Liquid, ObjectDef, ObjectId, ObjectType, Page, Portal,
+    WearLocation,
+};
+use crate::olc::{EditTarget, Editor, TextField};
+use crate::room::{
+    Closeable, Direction, Door, Exit, ExtraDescription, Lockable, Room, RoomFlag, RoomId,
+    SectorType,
+};
+use crate::util;
+use crate::world::World;
+use ahash::RandomState;
+use generational_arena::Index;
+use std::cell::Cell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{Result as IoResult, Write};
+
+pub fn redit(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let (arg, _) = util::take_argument(arguments);
+    let target = match arg.map(str::parse::<u32>) {
+        None => room_id,
+        Some(Ok(n)) => RoomId::from(n),
+        Some(Err(_)) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "Redit which room (by number)?\r\n");
+        }
+    };
+    // New rooms go into whichever area the builder is standing in.
+    let area_idx = world
+        .rooms
+        .get(&target)
+        .or_else(|| world.rooms.get(&room_id))
+        .map(|room| room.area)
+        .expect("Unwrapped None room");
+    if !check_permission(conn_idx, area_idx, world)? {
+        return Ok(());
+    }
+
+    if let Entry::Vacant(entry) = world.rooms.entry(target) {
+        entry.insert(Room {
+            id: target,
+            area: area_idx,
+            name: String::from("A new room"),
+            ..Default::default()
+        });
+        world.room_chars.insert(target, vec![]);
+        world.room_objs.insert(target, Default::default());
+        world.areas[area_idx].rooms.push(target);
+    }
+    start_editing(conn_idx, EditTarget::Room(target), area_idx, world)
+}
+
+pub fn oedit(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let (arg, _) = util::take_argument(arguments);
+    let target = match arg.map(str::parse::<usize>) {
+        Some(Ok(n)) => ObjectId::from(n),
+        _ => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "Oedit which object (by number)?\r\n");
+        }
+    };
+    let area_idx = world
+        .areas
+        .iter()
+        .position(|area| area.objects.contains(&target))
+        .unwrap_or_else(|| world.rooms[&room_id].area);
+    if !check_permission(conn_idx, area_idx, world)? {
+        return Ok(());
+    }
+
+    if let Entry::Vacant(entry) = world.object_defs.entry(target) {
+        entry.insert(ObjectDef {
+            id: target,
+            keywords: vec![String::from("new")],
+            name: String::from("a new object"),
+            room_description: String::from("A new object lies here."),
+            ..Default::default()
+        });
+        world.areas[area_idx].objects.push(target);
+    }
+    start_editing(conn_idx, EditTarget::Object(target), area_idx, world)
+}
+
+pub fn medit(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let (arg, _) = util::take_argument(arguments);
+    let target = match arg.map(str::parse::<u32>) {
+        Some(Ok(n)) if n > 0 => CharId::from(n),
+        _ => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "Medit which npc (by number)?\r\n");
+        }
+    };
+    let area_idx = world
+        .areas
+        .iter()
+        .position(|area| area.npcs.contains(&target))
+        .unwrap_or_else(|| world.rooms[&room_id].area);
+    if !check_permission(conn_idx, area_idx, world)? {
+        return Ok(());
+    }
+
+    if let Entry::Vacant(entry) = world.npc_defs.entry(target) {
+        entry.insert(CharacterData {
+            id: target,
+            keywords: vec![String::from("new")],
+            formal_name: String::from("a new npc"),
+            in_room: room_id,
+            ..Default::default()
+        });
+        world.areas[area_idx].npcs.push(target);
+    }
+    start_editing(conn_idx, EditTarget::Npc(target), area_idx, world)
+}
+
+pub fn interpret(
+    conn_idx: Index,
+    _room_id: RoomId,
+    input: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let editor = match conn.editor.as_mut() {
+        Some(editor) => editor,
+        None => return Ok(()),
+    };
+    let target = editor.target;
+    let area_idx = editor.area;
+
+    if let Some(buffer) = editor.text.as_mut() {
+        let line = input.trim_end_matches(&['\r', '\n'][..]);
+        if line.trim() != "@" {
+            buffer.text.push_str(line);
+            buffer.text.push('\n');
+            return Ok(());
+        }
+        let buffer = editor.text.take().unwrap();
+        let message = set_text(target, buffer.field, buffer.text, world);
+        let conn = world.connections.get_mut(conn_idx).unwrap();
+        return write!(conn, "{}\r\n", message);
+    }
+
+    let (command, rest) = match util::take_command(input) {
+        Some((command, rest)) => (command.to_ascii_lowercase(), rest.trim()),
+        None => return show(conn_idx, world),
+    };
+    let message = match command.as_str() {
+        "show" | "?" => return show(conn_idx, world),
+        "done" => {
+            conn.editor = None;
+            String::from("You stop editing. Don't forget to save!")
+        }
+        "save" => match world.save_area(area_idx) {
+            Ok(()) => format!("Saved area {}.", world.areas[area_idx].file_name),
+            Err(e) => {
+                log::error!(
+                    "Error saving area {}: {:?}",
+                    world.areas[area_idx].file_name,
+                    e
+                );
+                String::from("The area couldn't be saved.")
+            }
+        },
+        "desc" | "description" => {
+            editor.start_text(TextField::Description);
+            String::from("Enter the new description. End it with a line containing only @")
+        }
+        "nightdesc" if matches!(target, EditTarget::Room(_)) => {
+            editor.start_text(TextField::NightDescription);
+            String::from(
+                "Enter the description for night time. End it with a line containing only @ (an empty one removes it)",
+            )
+        }
+        "extra" if matches!(target, EditTarget::Room(_)) => {
+            let keywords = parse_keywords(rest);
+            if keywords.is_empty() {
+                String::from("Extra description for which keywords?")
+            } else {
+                editor.start_text(TextField::ExtraDescription(keywords));
+                String::from(
+                    "Enter the extra description. End it with a line containing only @ (an empty one removes it)",
+                )
+            }
+        }
+        "page" if matches!(target, EditTarget::Object(_)) => {
+            if rest.is_empty() {
+                String::from("What's the title of the page?")
+            } else {
+                editor.start_text(TextField::Page(rest.to_string()));
+                String::from(
+                    "Enter the page. End it with a line containing only @ (an empty one removes it)",
+                )
+            }
+        }
+        _ => match target {
+            EditTarget::Room(id) => edit_room(id, &command, rest, world),
+            EditTarget::Object(id) => edit_object(id, &command, rest, world),
+            EditTarget::Npc(id) => edit_npc(id, &command, rest, world),
+        },
+    };
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    write!(conn, "{}\r\n", message)
+}
+
+fn check_permission(conn_idx: Index, area_idx: usize, world: &mut World) -> IoResult<bool> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let file_name = &world.areas[area_idx].file_name;
+    if !conn.player().is_builder() {
+        write!(conn, "I have no idea what that means!\r\n")?;
+        Ok(false)
+    } else if !conn.player().can_build(file_name) {
+        write!(conn, "You aren't a builder for {}.\r\n", file_name)?;
+        Ok(false)
+    } else {
+        Ok(true)
+    }
+}
+
+fn start_editing(
+    conn_idx: Index,
+    target: EditTarget,
+    area_idx: usize,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    conn.editor = Some(Editor::new(target, area_idx));
+    show(conn_idx, world)
+}
+
+fn show(conn_idx: Index, world: &mut World) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let editor = match &conn.editor {
+        Some(editor) => editor,
+        None => return Ok(()),
+    };
+    let area = &world.areas[editor.area].file_name;
+    match editor.target {
+        EditTarget::Room(id) => {
+            let room = world.rooms.get(&id).expect("Unwrapped None room");
+            write!(conn, "Room {} in area {}\r\n", id, area)?;
+            write!(conn, "  name:  {}\r\n", room.name)?;
+            write!(conn, "  desc:\r\n{}\r\n", room.description)?;
+            if let Some(night) = &room.night_description {
+                write!(conn, "  night desc:\r\n{}\r\n", night)?;
+            }
+            write!(conn, "  exits:\r\n")?;
+            for exit in room.exits.as_ref() {
+                write!(
+                    conn,
+                    "    {:<6} -> {} ({:?}, key {:?}){}\r\n",
+                    exit.dir.leaving(),
+                    exit.to,
+                    exit.door,
+                    exit.key,
+                    if exit.hidden { " hidden" } else { "" }
+                )?;
+                if exit.door.is_present() {
+                    write!(conn, "           door name: {}\r\n", exit.door_name())?;
+                }
+                if !exit.keywords.is_empty() {
+                    write!(conn, "           keywords: {}\r\n", exit.keywords.join(" "))?;
+                }
+                if let Some(description) = &exit.description {
+                    write!(conn, "           desc: {}\r\n", description)?;
+                }
+            }
+            write!(conn, "  load:  {:?}\r\n", room.object_ids)?;
+            write!(conn, "  flags: {:?}\r\n", room.flags)?;
+            write!(conn, "  sector: {:?}\r\n", room.sector)?;
+            for extra in &room.extra_descs {
+                write!(conn, "  extra: {}\r\n", extra.keywords.join(" "))?;
+            }
+            write!(
+                conn,
+                "Commands: name <text>, desc, exit <dir> <room|delete|hidden|keywords <words>|desc <text>>, door <dir> <none|closable|lockable|key <object>|name <text>>, load <object>, unload <object>, flag <flag>, sector <sector>, extra <keywords>, nightdesc, show, save, done\r\n"
+            )
+        }
+        EditTarget::Object(id) => {
+            let obj = world
+                .object_defs
+                .get(&id)
+                .expect("Unwrapped None object def");
+            write!(conn, "Object {} in area {}\r\n", id, area)?;
+            write!(conn, "  keywords: {}\r\n", obj.keywords.join(" "))?;
+            write!(conn, "  name:     {}\r\n", obj.name)?;
+            write!(conn, "  roomdesc: {}\r\n", obj.room_description)?;
+            write!(conn, "  type:     {:?}\r\n", obj.object_type)?;
+            if let Some(wear) = obj.wear {
+                write!(conn, "  wear:     {:?}\r\n", wear)?;
+            }
+            write!(conn, "  weight:   {}\r\n", obj.weight)?;
+            write!(conn, "  value:    {}\r\n", obj.value)?;
+            if let Some(container) = &obj.container {
+                write!(
+                    conn,
+                    "  container: holds {}, lid {:?}, key {:?}\r\n",
+                    container.capacity,
+                    container.lid.get(),
+                    container.key
+                )?;
+            }
+            if obj.object_type == ObjectType::Light {
+                match obj.light_hours {
+                    Some(hours) => write!(conn, "  light:    {} hours\r\n", hours)?,
+                    None => write!(conn, "  light:    forever\r\n")?,
+                }
+            }
+            if let Some(food) = &obj.food {
+                write!(
+                    conn,
+                    "  food:     {} hours{}\r\n",
+                    food.hours,
+                    if food.poisoned { ", poisoned" } else { "" }
+                )?;
+            }
+            if let Some(drink) = &obj.drink {
+                let holds = if drink.fountain {
+                    String::from("fountain")
+                } else {
+                    format!("{}/{}", drink.amount.get(), drink.capacity)
+                };
+                write!(
+                    conn,
+                    "  drink:    {} of {}{}\r\n",
+                    holds,
+                    drink.liquid.get().name(),
+                    if drink.poisoned.get() {
+                        ", poisoned"
+                    } else {
+                        ""
+                    }
+                )?;
+            }
+            if let Some(book) = &obj.book {
+                write!(
+                    conn,
+                    "  book:     {}\r\n",
+                    if book.writable { "writable" } else { "fixed" }
+                )?;
+                for (idx, page) in book.pages.borrow().iter().enumerate() {
+                    write!(conn, "  page {:2}:  {}\r\n", idx + 1, page.title)?;
+                }
+            }
+            if let Some(portal) = &obj.portal {
+                let leads_to = match (portal.pair, portal.to, &portal.to_area) {
+                    (Some(pair), _, _) => format!("wherever object {} is", pair),
+                    (None, Some(to), _) => format!("room {}", to),
+                    (None, None, Some(area)) => format!("anywhere in {}", area),
+                    (None, None, None) => String::from("nowhere"),
+                };
+                write!(
+                    conn,
+                    "  portal:   to {}, charges {:?}, decay {:?}\r\n",
+                    leads_to,
+                    portal.charges.get(),
+                    portal.decay.get()
+                )?;
+            }
+            if let Some(furniture) = &obj.furniture {
+                write!(
+                    conn,
+                    "  furniture: seats {}, allows {:?}\r\n",
+                    furniture.seats, furniture.allows
+                )?;
+            }
+            if let Some(medicine) = &obj.medicine {
+                write!(conn, "  cures:    {:?}\r\n", medicine.cures)?;
+                for effect in &medicine.effects {
+                    write!(
+                        conn,
+                        "  effect:   {:?} for {} hours\r\n",
+                        effect.kind, effect.hours
+                    )?;
+                }
+            }
+            write!(
+                conn,
+                "  desc:\r\n{}\r\n",
+                obj.description.as_deref().unwrap_or("")
+            )?;
+            write!(
+                conn,
+                "Commands: keywords <words>, name <text>, roomdesc <text>, type <type>, wear <location|none>, weight <n>, value <n>, light <hours|forever>, container <capacity|none>, lid <none|closable|lockable>, lock <key|none>, food <hours|none>, drink <capacity|fountain|none>, liquid <liquid>, poison, cure <effect>, effect <effect> <hours>, book <fixed|writable|none>, page <title>, portal <room|area <file>|pair <object>|none>, charges <n|unlimited>, decay <hours|never>, furniture <seats|none>, allows <position>, desc, show, save, done\r\n"
+            )
+        }
+        EditTarget::Npc(id) => {
+            let npc = world.npc_defs.get(&id).expect("Unwrapped None npc def");
+            write!(conn, "Npc {} in area {}\r\n", id, area)?;
+            write!(conn, "  keywords: {}\r\n", npc.keywords.join(" "))?;
+            write!(conn, "  name:     {}\r\n", npc.formal_name)?;
+            write!(
+                conn,
+                "  roomdesc: {}\r\n",
+                npc.room_description.as_deref().unwrap_or("")
+            )?;
+            write!(conn, "  pronoun:  {}\r\n", npc.pronoun.subject())?;
+            write!(conn, "  position: {:?}\r\n", npc.position)?;
+            write!(conn, "  level:    {}\r\n", npc.experience.level)?;
+            write!(conn, "  hp:       {}\r\n", npc.vitals.max_hp)?;
+            let stats = &npc.attributes;
+            write!(
+                conn,
+                "  stats:    str {} int {} wis {} dex {} con {}\r\n",
+                stats.strength,
+                stats.intelligence,
+                stats.wisdom,
+                stats.dexterity,
+                stats.constitution
+            )?;
+            write!(conn, "  room:     {}\r\n", npc.in_room)?;
+            write!(conn, "  gold:     {}\r\n", npc.gold)?;
+            if let Some(shop) = &npc.shop {
+                let stock: Vec<String> = shop.stock.iter().map(ObjectId::to_string).collect();
+                write!(conn, "  stock:    {}\r\n", stock.join(" "))?;
+                write!(conn, "  buys:     {:?}\r\n", shop.buys)?;
+                write!(
+                    conn,
+                    "  prices:   sells at {}%, buys at {}%\r\n",
+                    shop.sells_at, shop.buys_at
+                )?;
+                write!(conn, "  hours:    {} to {}\r\n", shop.opens, shop.closes)?;
+            }
+            write!(
+                conn,
+                "  desc:\r\n{}\r\n",
+                npc.description.as_deref().unwrap_or("")
+            )?;
+            write!(
+                conn,
+                "Commands: keywords <words>, name <text>, roomdesc <text>, pronoun <it|he|she|they>, position <position>, level <n>, hp <n>, stats <str> <int> <wis> <dex> <con>, room <room>, gold <n>, shop <on|none>, stock <object>, buys <type>, prices <sell%> <buy%>, hours <open> <close>, desc, show, save, done\r\n"
+            )
+        }
+    }
+}
+
+fn set_text(target: EditTarget, field: TextField, text: String, world: &mut World) -> String {
+    match (field, target) {
+        (TextField::ExtraDescription(keywords), EditTarget::Room(id)) => {
+            return set_extra_description(id, keywords, text, world);
+        }
+        (TextField::NightDescription, EditTarget::Room(id)) => {
+            if let Some(room) = world.rooms.get_mut(&id) {
+                room.night_description =
+                    Some(text.trim().to_string()).filter(|text| !text.is_empty());
+            }
+            return String::from("Night description set.");
+        }
+        (TextField::Page(title), EditTarget::Object(id)) => {
+            return set_page(id, title, text, world);
+        }
+        (TextField::Description, _) => {}
+        (TextField::Page(_), _) => return String::from("Only objects have that."),
+        _ => return String::from("Only rooms have that."),
+    }
+    match target {
+        EditTarget::Room(id) => {
+            if let Some(room) = world.rooms.get_mut(&id) {
+                room.description = text.trim().to_string();
+            }
+        }
+        EditTarget::Object(id) => {
+            if let Some(obj) = world.object_defs.get_mut(&id) {
+                obj.description = Some(text);
+            }
+        }
+        EditTarget::Npc(id) => {
+            if let Some(npc) = world.npc_defs.get_mut(&id) {
+                npc.description = Some(text);
+            }
+        }
+    }
+    String::from("Description set.")
+}
+
+fn set_extra_description(
+    id: RoomId,
+    keywords: Vec<String>,
+    text: String,
+    world: &mut World,
+) -> String {
+    let room = match world.rooms.get_mut(&id) {
+        Some(room) => room,
+        None => return String::from("The room is gone."),
+    };
+    room.extra_descs.retain(|extra| extra.keywords != keywords);
+    if text.trim().is_empty() {
+        return String::from("Extra description removed.");
+    }
+    room.extra_descs.push(ExtraDescription {
+        keywords,
+        description: text,
+    });
+    String::from("Extra description set.")
+}
+
+// Rewrites the page with the same title, or adds a new one at the end.
+fn set_page(id: ObjectId, title: String, text: String, world: &mut World) -> String {
+    let book = match world.object_defs.get_mut(&id) {
+        Some(obj) => obj.book.get_or_insert_with(Default::default),
+        None => return String::from("The object is gone."),
+    };
+    let mut pages = book.pages.borrow_mut();
+    let existing = pages.iter().position(|page| page.title == title);
+    match (existing, text.trim().is_empty()) {
+        (Some(idx), true) => {
+            pages.remove(idx);
+            String::from("Page removed.")
+        }
+        (None, true) => String::from("Page left out."),
+        (Some(idx), false) => {
+            pages[idx].text = text;
+            String::from("Page set.")
+        }
+        (None, false) => {
+            pages.push(Page { title, text });
+            String::from("Page added.")
+        }
+    }
+}
+
+fn parse_keywords(mut rest: &str) -> Vec<String> {
+    let mut keywords = vec![];
+    while let (Some(kw), remainder) = util::take_argument(rest) {
+        keywords.push(kw.to_ascii_lowercase());
+        rest = remainder;
+    }
+    keywords
+}
+
+fn edit_room(id: RoomId, command: &str, rest: &str, world: &mut World) -> String {
+    match command {
+        "name" if !rest.is_empty() => {
+            world.rooms.get_mut(&id).unwrap().name = rest.to_string();
+            String::from("Name set.")
+        }
+        "exit" => {
+            let (dir, rest) = util::take_argument(rest);
+            let (to, text) = util::take_argument(rest);
+            let dir = match dir.and_then(Direction::parse) {
+                Some(dir) => dir,
+                None => return String::from("Which direction?"),
+            };
+            let room = world.rooms.get_mut(&id).unwrap();
+            let position = room.exits.as_ref().iter().position(|exit| exit.dir == dir);
+            match (to, position) {
+                (Some("delete"), Some(n)) => {
+                    room.exits.remove(n);
+                    format!("Exit {} removed.", dir.leaving())
+                }
+                (Some("hidden"), Some(n)) => {
+                    let exit = &mut room.exits.0[n];
+                    exit.hidden = !exit.hidden;
+                    if exit.hidden {
+                        format!("Exit {} is now hidden.", dir.leaving())
+                    } else {
+                        format!("Exit {} is no longer hidden.", dir.leaving())
+                    }
+                }
+                (Some("keywords"), Some(n)) => {
+                    room.exits.0[n].keywords = parse_keywords(text);
+                    String::from("Exit keywords set.")
+                }
+                (Some("desc"), Some(n)) => {
+                    room.exits.0[n].description =
+                        Some(text.trim().to_string()).filter(|description| !description.is_empty());
+                    String::from("Exit description set.")
+                }
+                (Some("delete" | "hidden" | "keywords" | "desc"), None) => {
+                    format!("There's no exit {}.", dir.leaving())
+                }
+                (Some(to), _) => match to.parse::<u32>() {
+                    // Every exit has to lead somewhere, or walking through it would go nowhere.
+                    Ok(n) if !world.rooms.contains_key(&RoomId::from(n)) => {
+                        format!("Room {} doesn't exist. Create it with redit first.", n)
+                    }
+                    Ok(n) => {
+                        let to = RoomId::from(n);
+                        let leaving = dir.leaving().to_string();
+                        let room = world.rooms.get_mut(&id).unwrap();
+                        match position {
+                            Some(n) => room.exits.0[n].to = to,
+                            None => room.exits.insert(Exit::new(to, dir)),
+                        }
+                        format!("Exit {} now leads to room {}.", leaving, to)
+                    }
+                    Err(_) => String::from("Exit to which room (by number)?"),
+                },
+                (None, _) => String::from("Exit to which room?"),
+            }
+        }
+        "door" => {
+            let (dir, rest) = util::take_argument(rest);
+            let (kind, rest) = util::take_argument(rest);
+            let room = world.rooms.get_mut(&id).unwrap();
+            let exit = match dir.and_then(|dir| room.exits.get_mut(dir)) {
+                Some(exit) => exit,
+                None => return String::from("There's no exit that way."),
+            };
+            exit.door = match kind {
+                Some("key") => {
+                    let (key, _) = util::take_argument(rest);
+                    exit.key = match key {
+                        Some("none") => None,
+                        Some(key) => match key.parse::<usize>() {
+                            Ok(n) => Some(ObjectId::from(n)),
+                            Err(_) => {
+                                return String::from("Which key object (by number), or none?")
+                            }
+                        },
+                        None => return String::from("Which key object (by number), or none?"),
+                    };
+                    return match exit.key {
+                        Some(key) => format!("Door {} now uses key {}.", exit.dir.leaving(), key),
+                        None => format!("Door {} no longer has a key.", exit.dir.leaving()),
+                    };
+                }
+                Some("name") => {
+                    let name = rest.trim();
+                    exit.door_name = Some(name.to_string()).filter(|name| !name.is_empty());
+                    return format!(
+                        "Door {} is now called {}.",
+                        exit.dir.leaving(),
+                        exit.door_name()
+                    );
+                }
+                Some("none") => Door::None,
+                Some("closable") => Door::Closable(Closeable::Closed),
+                Some("lockable") => Door::Lockable(Closeable::Closed, Lockable::Locked),
+                _ => return String::from("A door can be none, closable, or lockable."),
+            };
+            format!("Door {} set to {:?}.", exit.dir.leaving(), exit.door)
+        }
+        "load" | "unload" => {
+            let (obj, _) = util::take_argument(rest);
+            let obj = match obj.map(str::parse::<usize>) {
+                Some(Ok(n)) => ObjectId::from(n),
+                _ => return String::from("Which object (by number)?"),
+            };
+            if command == "load" && !world.object_defs.contains_key(&obj) {
+                return format!("There's no object {}.", obj);
+            }
+            let room = world.rooms.get_mut(&id).unwrap();
+            if command == "load" {
+                room.object_ids.push(obj);
+                format!("Object {} will load here.", obj)
+            } else if let Some(n) = room.object_ids.iter().position(|id| *id == obj) {
+                room.object_ids.remove(n);
+                format!("Object {} will no longer load here.", obj)
+            } else {
+                format!("Object {} doesn't load here.", obj)
+            }
+        }
+        "flag" => match RoomFlag::parse(rest) {
+            Some(flag) => {
+                let room = world.rooms.get_mut(&id).unwrap();
+                if room.has_flag(flag) {
+                    room.flags.retain(|f| *f != flag);
+                    format!("Flag {:?} removed.", flag)
+                } else {
+                    room.flags.push(flag);
+                    format!("Flag {:?} added.", flag)
+                }
+            }
+            None => format!("Room flags are: {:?}", RoomFlag::ALL),
+        },
+        "sector" => match SectorType::parse(rest) {
+            Some(sector) => {
+                world.rooms.get_mut(&id).unwrap().sector = sector;
+                format!("Sector set to {:?}.", sector)
+            }
+            None => format!("Sectors are: {:?}", SectorType::ALL),
+        },
+        _ => String::from("Unknown room field. Type ? for the menu."),
+    }
+}
+
+fn edit_object(id: ObjectId, command: &str, rest: &str, world: &mut World) -> String {
+    let obj = world.object_defs.get_mut(&id).unwrap();
+    match command {
+        "keywords" => {
+            let keywords = parse_keywords(rest);
+            if keywords.is_empty() {
+                return String::from("An object needs at least one keyword.");
+            }
+            obj.keywords = keywords;
+            String::from("Keywords set.")
+        }
+        "name" if !rest.is_empty() => {
+            obj.name = rest.to_string();
+            String::from("Name set.")
+        }
+        "roomdesc" if !rest.is_empty() => {
+            obj.room_description = rest.to_string();
+            String::from("Room description set.")
+        }
+        "type" => match ObjectType::parse(rest) {
+            Some(object_type) => {
+                obj.object_type = object_type;
+                format!("Type set to {:?}.", object_type)
+            }
+            None => format!("Object types are: {:?}", ObjectType::ALL),
+        },
+        "container" => {
+            obj.container = match rest.parse::<usize>() {
+                Ok(capacity) => Some(Container {
+                    capacity,
+                    ..obj.container.take().unwrap_or_default()
+                }),
+                Err(_) if rest == "none" => None,
+                Err(_) => return String::from("How many things should it hold, or none?"),
+            };
+            String::from("Container set.")
+        }
+        "lid" | "lock" => {
+            let container = match obj.container.as_mut() {
+                Some(container) => container,
+                None => return String::from("Make it a container first."),
+            };
+            if command == "lock" {
+                container.key = match rest.parse::<usize>() {
+                    Ok(n) => Some(ObjectId::from(n)),
+                    Err(_) if rest == "none" => None,
+                    Err(_) => return String::from("Which key object (by number), or none?"),
+                };
+                return String::from("Key set.");
+            }
+            container.lid.set(match rest {
+                "none" => Door::None,
+                "closable" => Door::Closable(Closeable::Closed),
+                "lockable" => Door::Lockable(Closeable::Closed, Lockable::Locked),
+                _ => return String::from("A lid can be none, closable, or lockable."),
+            });
+            String::from("Lid set.")
+        }
+        "weight" => match rest.parse() {
+            Ok(weight) => {
+                obj.weight = weight;
+                String::from("Weight set.")
+            }
+            Err(_) => String::from("How heavy should it be?"),
+        },
+        "value" => match rest.parse() {
+            Ok(value) => {
+                obj.value = value;
+                String::from("Value set.")
+            }
+            Err(_) => String::from("How much should it be worth?"),
+        },
+        "wear" => {
+            obj.wear = match WearLocation::parse(rest) {
+                Some(location) => Some(location),
+                None if rest == "none" => None,
+                None => return format!("Wear locations are: {:?}", WearLocation::ALL),
+            };
+            String::from("Wear location set.")
+        }
+        "light" => {
+            obj.light_hours = match rest.parse::<u32>() {
+                Ok(hours) => Some(hours),
+                Err(_) if rest == "forever" => None,
+                Err(_) => return String::from("Light for how many hours, or forever?"),
+            };
+            String::from("Light duration set.")
+        }
+        "food" => {
+            obj.food = match rest.parse::<u32>() {
+                Ok(hours) => Some(Food {
+                    hours,
+                    ..obj.food.take().unwrap_or_default()
+                }),
+                Err(_) if rest == "none" => None,
+                Err(_) => return String::from("How many hours should it feed, or none?"),
+            };
+            String::from("Food set.")
+        }
+        "drink" => {
+            let drink = obj.drink.take().unwrap_or_default();
+            obj.drink = match rest.parse::<u32>() {
+                Ok(capacity) => Some(Drink {
+                    capacity,
+                    amount: Cell::new(capacity),
+                    fountain: false,
+                    ..drink
+                }),
+                Err(_) if rest == "fountain" => Some(Drink {
+                    fountain: true,
+                    ..drink
+                }),
+                Err(_) if rest == "none" => None,
+                Err(_) => {
+                    return String::from("How many drinks should it hold, fountain, or none?")
+                }
+            };
+            String::from("Drink set.")
+        }
+        "liquid" => match (obj.drink.as_ref(), Liquid::parse(rest)) {
+            (None, _) => String::from("Make it a drink first."),
+            (Some(drink), Some(liquid)) => {
+                drink.liquid.set(liquid);
+                format!("Liquid set to {}.", liquid.name())
+            }
+            (Some(_), None) => format!("Liquids are: {:?}", Liquid::ALL),
+        },
+        "poison" => {
+            let poisoned = match (obj.food.as_mut(), obj.drink.as_ref()) {
+                (Some(food), _) => {
+                    food.poisoned = !food.poisoned;
+                    food.poisoned
+                }
+                (None, Some(drink)) => {
+                    drink.poisoned.set(!drink.poisoned.get());
+                    drink.poisoned.get()
+                }
+                (None, None) => return String::from("Only food and drink can be poisoned."),
+            };
+            String::from(if poisoned {
+                "Poisoned."
+            } else {
+                "No longer poisoned."
+            })
+        }
+        "book" => {
+            obj.book = match rest {
+                "fixed" | "writable" => Some(Book {
+                    writable: rest == "writable",
+                    ..obj.book.take().unwrap_or_default()
+                }),
+                "none" => None,
+                _ => return String::from("A book can be fixed, writable, or none."),
+            };
+            String::from("Book set.")
+        }
+        "portal" => {
+            let (kind, target) = util::take_argument(rest);
+            let target = target.trim();
+            let portal = match (kind, target.parse::<usize>()) {
+                (Some("none"), _) => {
+                    obj.portal = None;
+                    return String::from("Portal removed.");
+                }
+                (Some("area"), _) if !target.is_empty() => Portal {
+                    to_area: Some(target.to_string()),
+                    ..Default::default()
+                },
+                (Some("pair"), Ok(pair)) => Portal {
+                    pair: Some(ObjectId::from(pair)),
+                    ..Default::default()
+                },
+                (Some(room), _) => match room.parse::<u32>() {
+                    Ok(room) => Portal {
+                        to: Some(RoomId::from(room)),
+                        ..Default::default()
+                    },
+                    Err(_) => return String::from("Lead to which room, area, or paired object?"),
+                },
+                (None, _) => return String::from("Lead to which room, area, or paired object?"),
+            };
+            // Keep any limits it already had.
+            if let Some(old) = obj.portal.take() {
+                portal.charges.set(old.charges.get());
+                portal.decay.set(old.decay.get());
+            }
+            obj.portal = Some(portal);
+            String::from("Portal set.")
+        }
+        "charges" | "decay" => {
+            let portal = match obj.portal.as_ref() {
+                Some(portal) => portal,
+                None => return String::from("Make it a portal first."),
+            };
+            let limit = match rest.parse::<u32>() {
+                Ok(n) if n > 0 => Some(n),
+                _ if rest == "unlimited" || rest == "never" => None,
+                _ if command == "charges" => {
+                    return String::from("How many charges, or unlimited?")
+                }
+                _ => return String::from("How many hours, or never?"),
+            };
+            if command == "charges" {
+                portal.charges.set(limit);
+                String::from("Charges set.")
+            } else {
+                portal.decay.set(limit);
+                String::from("Decay set.")
+            }
+        }
+        "furniture" => {
+            obj.furniture = match rest.parse::<usize>() {
+                Ok(seats) if seats > 0 => Some(Furniture {
+                    seats,
+                    ..obj.furniture.take().unwrap_or_default()
+                }),
+                _ if rest == "none" => None,
+                _ => return String::from("How many can use it at once, or none?"),
+            };
+            String::from("Furniture set.")
+        }
+        "allows" => match (obj.furniture.as_mut(), Position::parse(rest)) {
+            (None, _) => String::from("Make it furniture first."),
+            (Some(furniture), Some(position))
+                if [Position::Sitting, Position::Resting, Position::Sleeping]
+                    .contains(&position) =>
+            {
+                if furniture.allows(position) {
+                    furniture.allows.retain(|allowed| *allowed != position);
+                    format!("No longer allows {:?}.", position)
+                } else {
+                    furniture.allows.push(position);
+                    format!("Allows {:?}.", position)
+                }
+            }
+            (Some(_), _) => String::from("Furniture can allow Sitting, Resting, or Sleeping."),
+        },
+        "cure" => match EffectKind::parse(rest) {
+            Some(kind) => {
+                let medicine = obj.medicine.get_or_insert_with(Default::default);
+                if medicine.cures.contains(&kind) {
+                    medicine.cures.retain(|cure| *cure != kind);
+                    format!("No longer cures {:?}.", kind)
+                } else {
+                    medicine.cures.push(kind);
+                    format!("Cures {:?}.", kind)
+                }
+            }
+            None => format!("Effects are: {:?}", EffectKind::ALL),
+        },
+        "effect" => {
+            let (kind, hours) = util::take_argument(rest);
+            match (
+                kind.and_then(EffectKind::parse),
+                hours.trim().parse::<u32>(),
+            ) {
+                (Some(kind), Ok(hours)) => {
+                    let medicine = obj.medicine.get_or_insert_with(Default::default);
+                    medicine.effects.retain(|effect| effect.kind != kind);
+                    if hours == 0 {
+                        return format!("No longer gives {:?}.", kind);
+                    }
+                    medicine.effects.push(Effect::new(kind, hours));
+                    format!("Gives {:?} for {} hours.", kind, hours)
+                }
+                _ => format!(
+                    "Give which effect for how many hours (0 to remove)? Effects are: {:?}",
+                    EffectKind::ALL
+                ),
+            }
+        }
+        _ => String::from("Unknown object field. Type ? for the menu."),
+    }
+}
+
+fn edit_npc(id: CharId, command: &str, rest: &str, world: &mut World) -> String {
+    let rooms = &world.rooms;
+    let object_defs = &world.object_defs;
+    let npc = world.npc_defs.get_mut(&id).unwrap();
+    match command {
+        "keywords" => {
+            let keywords = parse_keywords(rest);
+            if keywords.is_empty() {
+                return String::from("An npc needs at least one keyword.");
+            }
+            npc.keywords = keywords;
+            String::from("Keywords set.")
+        }
+        "name" if !rest.is_empty() => {
+            npc.formal_name = rest.to_string();
+            String::from("Name set.")
+        }
+        "roomdesc" => {
+            npc.room_description = if rest.is_empty() {
+                None
+            } else {
+                Some(rest.to_string())
+            };
+            String::from("Room description set.")
+        }
+        "pronoun" => {
+            npc.pronoun = match rest.to_ascii_lowercase().as_str() {
+                "it" => Pronoun::It,
+                "he" => Pronoun::He,
+                "she" => Pronoun::She,
+                "they" => Pronoun::They,
+                _ => return String::from("Pronouns are it, he, she, or they."),
+            };
+            String::from("Pronoun set.")
+        }
+        "room" => match rest.parse::<u32>().map(RoomId::from) {
+            Ok(room_id) if rooms.contains_key(&room_id) => {
+                npc.in_room = room_id;
+                format!("Npc will load in room {}.", room_id)
+            }
+            _ => String::from("Which room (by number)?"),
+        },
+        "position" => match Position::parse(rest) {
+            Some(position) => {
+                npc.position = position;
+                format!("Position set to {:?}.", position)
+            }
+            None => format!("Positions are: {:?}", Position::ALL),
+        },
+        "level" => match rest.parse() {
+            Ok(level) if (1..=MAX_LEVEL).contains(&level) => {
+                npc.experience.level = level;
+                String::from("Level set.")
+            }
+            _ => format!("Which level, from 1 to {}?", MAX_LEVEL),
+        },
+        "hp" => match rest.parse() {
+            Ok(hp) if hp > 0 => {
+                npc.vitals.hp = hp;
+                npc.vitals.max_hp = hp;
+                String::from("Hit points set.")
+            }
+            _ => String::from("How many hit points?"),
+        },
+        "stats" => {
+            let stats: Vec<u32> = rest
+                .split_whitespace()
+                .filter_map(|word| word.parse().ok())
+                .collect();
+            match stats.as_slice() {
+                [strength, intelligence, wisdom, dexterity, constitution] => {
+                    npc.attributes = Attributes {
+                        strength: *strength,
+                        intelligence: *intelligence,
+                        wisdom: *wisdom,
+                        dexterity: *dexterity,
+                        constitution: *constitution,
+                    };
+                    String::from("Stats set.")
+                }
+                _ => String::from(
+                    "Give strength, intelligence, wisdom, dexterity, and constitution.",
+                ),
+            }
+        }
+        "gold" => match rest.parse() {
+            Ok(gold) => {
+                npc.gold = gold;
+                String::from("Gold set.")
+            }
+            Err(_) => String::from("How much gold?"),
+        },
+        "shop" => match rest {
+            "on" => {
+                npc.shop.get_or_insert_with(Shop::default);
+                String::from("Npc keeps a shop.")
+            }
+            "none" => {
+                npc.shop = None;
+                String::from("Shop removed.")
+            }
+            _ => String::from("Shop on or none?"),
+        },
+        "stock" | "buys" | "prices" | "hours" => match npc.shop.as_mut() {
+            Some(shop) => edit_shop(shop, command, rest, object_defs),
+            None => String::from("Turn the shop on first."),
+        },
+        _ => String::from("Unknown npc field. Type ? for the menu."),
+    }
+}
+
+fn edit_shop(
+    shop: &mut Shop,
+    command: &str,
+    rest: &str,
+    object_defs: &HashMap<ObjectId, ObjectDef, RandomState>,
+) -> String {
+    let numbers: Vec<u64> = rest
+        .split_whitespace()
+        .filter_map(|word| word.parse().ok())
+        .collect();
+    match (command, numbers.as_slice()) {
+        ("stock", [id]) => {
+            let id = ObjectId::from(*id as usize);
+            if let Some(pos) = shop.stock.iter().position(|stocked| *stocked == id) {
+                shop.stock.remove(pos);
+                return format!("Object {} removed from stock.", id);
+            }
+            if !object_defs.contains_key(&id) {
+                return format!("There's no object {}.", id);
+            }
+            shop.stock.push(id);
+            format!("Object {} added to stock.", id)
+        }
+        ("stock", _) => String::from("Stock which object (by number)?"),
+        ("buys", _) => match ObjectType::parse(rest) {
+            Some(ty) => {
+                if let Some(pos) = shop.buys.iter().position(|buys| *buys == ty) {
+                    shop.buys.remove(pos);
+                    format!("Shop no longer buys {:?}.", ty)
+                } else {
+                    shop.buys.push(ty);
+                    format!("Shop buys {:?}.", ty)
+                }
+            }
+            None => format!("Object types are: {:?}", ObjectType::ALL),
+        },
+        ("prices", [sells_at, buys_at]) => {
+            shop.sells_at = *sells_at as u32;
+            shop.buys_at = *buys_at as u32;
+            String::from("Prices set.")
+        }
+        ("prices", _) => String::from("Give the selling and buying percentages."),
+        ("hours", [opens, closes]) if *opens <= 24 && *closes <= 24 => {
+            shop.opens = *opens;
+            shop.closes = *closes;
+            String::from("Hours set.")
+        }
+        _ => String::from("Give the opening and closing hours, from 0 to 24."),
+    }
+}
@@ -0,0 +1,223 @@
+use crate::character::Position;
+use crate::object::Object;
+use crate::room::RoomId;
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::Result as IoResult;
+use std::rc::Rc;
+
+pub fn stand(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    change_position(conn_idx, room_id, arguments, Position::Standing, world)
+}
+
+pub fn sit(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    change_position(conn_idx, room_id, arguments, Position::Sitting, world)
+}
+
+pub fn rest(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    change_position(conn_idx, room_id, arguments, Position::Resting, world)
+}
+
+pub fn sleep(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    change_position(conn_idx, room_id, arguments, Position::Sleeping, world)
+}
+
+pub fn wake(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => return stand(conn_idx, room_id, arguments, world),
+    };
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world.characters.get(char_idx).unwrap();
+    if !char.position().is_awake() {
+        world.msg_char(
+            "You can't wake anyone else while you're asleep.",
+            Recipient::Subject(char_idx),
+        );
+        return Ok(());
+    }
+    let char_name = char.formal_name().to_string();
+    let characters = &world.characters;
+    let target = util::find_by_keyword(
+        world.room_chars[&room_id]
+            .iter()
+            .filter(|idx| **idx != char_idx)
+            .filter_map(|idx| characters.get(*idx)),
+        keyword,
+    );
+    let target = match target {
+        Some(target) => target,
+        None => {
+            world.msg_char("They aren't here.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let target_idx = target.index().unwrap();
+    let target_name = target.formal_name().to_string();
+    match target.position() {
+        Position::Dead => {
+            world.msg_char(
+                &format!("Nothing will wake {} now.", target_name),
+                Recipient::Subject(char_idx),
+            );
+        }
+        Position::Sleeping => {
+            world.msg_char(
+                &format!("You wake {}.", target_name),
+                Recipient::Subject(char_idx),
+            );
+            world.msg_char(
+                &format!("{} wakes {}.", char_name, target_name),
+                Recipient::Neither(char_idx, target_idx, room_id),
+            );
+            let target = world.characters.get_mut(target_idx).unwrap();
+            target.set_position(Position::Standing);
+            if target.connection().is_some() {
+                world.msg_char(
+                    &format!("{} wakes you.", char_name),
+                    Recipient::Subject(target_idx),
+                );
+            }
+        }
+        _ => {
+            world.msg_char(
+                &format!("{} is already awake.", util::capitalize(&target_name)),
+                Recipient::Subject(char_idx),
+            );
+        }
+    }
+    Ok(())
+}
+
+// Finds furniture in the room that has space for one more in that position.
+fn find_furniture(
+    char_idx: Index,
+    room_id: RoomId,
+    keyword: &str,
+    position: Position,
+    world: &World,
+) -> Result<Rc<Object>, String> {
+    let objs = &world.room_objs[&room_id];
+    let obj = util::find_item_by_keyword(objs, keyword)
+        .ok_or_else(|| format!("You don't see any {} here.", keyword))?;
+    if !obj
+        .furniture()
+        .is_some_and(|furniture| furniture.allows(position))
+    {
+        return Err(format!("You can't {} on {}.", verb(position), obj.name()));
+    }
+    let taken = world.room_chars[&room_id]
+        .iter()
+        .filter(|idx| **idx != char_idx)
+        .filter_map(|idx| world.characters.get(*idx))
+        .filter(|char| char.furniture().is_some_and(|on| std::ptr::eq(&**on, obj)))
+        .count();
+    if taken >= obj.furniture().unwrap().seats {
+        return Err(format!("There's no room on {}.", obj.name()));
+    }
+    // Walk to it, so the character can share the list's own pointer.
+    let mut cursor = objs.front();
+    while !cursor.get().is_some_and(|at| std::ptr::eq(at, obj)) {
+        cursor.move_next();
+    }
+    Ok(cursor.clone_pointer().unwrap())
+}
+
+fn verb(position: Position) -> &'static str {
+    match position {
+        Position::Sleeping => "sleep",
+        Position::Resting => "rest",
+        Position::Sitting => "sit",
+        _ => "stand",
+    }
+}
+
+fn change_position(
+    conn_idx: Index,
+    room_id: RoomId,
+    arguments: &str,
+    to: Position,
+    world: &mut World,
+) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let char = world.characters.get(char_idx).unwrap();
+    let from = char.position();
+    let keyword = util::take_argument(arguments)
+        .0
+        .filter(|_| to != Position::Standing);
+
+    let refusal = match (from, to) {
+        (Position::Fighting, _) => Some(String::from("You are already fighting!")),
+        (from, to) if from == to && keyword.is_none() => Some(format!(
+            "You are already {}.",
+            if to == Position::Sleeping {
+                "asleep"
+            } else {
+                to.verb()
+            }
+        )),
+        _ => None,
+    };
+    if let Some(refusal) = refusal {
+        world.msg_char(&refusal, Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    // Stay on the same furniture when it allows the new position.
+    let furniture = match keyword {
+        Some(keyword) => match find_furniture(char_idx, room_id, keyword, to, world) {
+            Ok(furniture) => Some(furniture),
+            Err(message) => {
+                world.msg_char(&message, Recipient::Subject(char_idx));
+                return Ok(());
+            }
+        },
+        None => char
+            .furniture()
+            .filter(|on| on.furniture().is_some_and(|furniture| furniture.allows(to)))
+            .cloned(),
+    };
+    let on = furniture
+        .as_ref()
+        .map(|obj| format!(" on {}", obj.name()))
+        .unwrap_or_default();
+    let char_name = char.formal_name().to_string();
+    let (you, they) = match (from, to) {
+        (Position::Sleeping, Position::Standing) => (
+            String::from("You wake and stand up."),
+            format!("{} wakes and stands up.", char_name),
+        ),
+        (_, Position::Standing) => (
+            String::from("You stand up."),
+            format!("{} stands up.", char_name),
+        ),
+        (Position::Sleeping, Position::Sitting) => (
+            format!("You wake and sit up{}.", on),
+            format!("{} wakes and sits up{}.", char_name, on),
+        ),
+        (_, Position::Sitting) => (
+            format!("You sit down{}.", on),
+            format!("{} sits down{}.", char_name, on),
+        ),
+        (Position::Sleeping, Position::Resting) => (
+            format!("You wake up and rest{}.", on),
+            format!("{} wakes up and rests{}.", char_name, on),
+        ),
+        (_, Position::Resting) => (
+            format!("You rest{}.", on),
+            format!("{} rests{}.", char_name, on),
+        ),
+        _ => (
+            format!("You go to sleep{}.", on),
+            format!("{} goes to sleep{}.", char_name, on),
+        ),
+    };
+
+    let char = world.characters.get_mut(char_idx).unwrap();
+    char.set_position(to);
+    char.set_furniture(furniture);
+    world.msg_char(&you, Recipient::Subject(char_idx));
+    world.msg_char(&they, Recipient::NotSubject(char_idx, room_id));
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use crate::character::Shop;
+use crate::room::RoomId;
+use crate::util::{self, Selection, Target};
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+
+// Finds a shopkeeper in the room who's open for business, and what they deal in.
+fn find_keeper(char_idx: Index, room_id: RoomId, world: &World) -> Result<(Index, Shop), String> {
+    let (keeper_idx, keeper) = world.room_chars[&room_id]
+        .iter()
+        .filter(|idx| **idx != char_idx)
+        .filter_map(|idx| world.characters.get(*idx).map(|char| (*idx, char)))
+        .find(|(_, char)| char.shop().is_some())
+        .ok_or_else(|| String::from("You can't do that here."))?;
+    let shop = keeper.shop().cloned().unwrap();
+    if !shop.is_open(world.time.hour) {
+        return Err(format!(
+            "{} tells you, 'Sorry, we're closed. Come back at {} o'clock.'",
+            util::capitalize(keeper.formal_name()),
+            shop.opens
+        ));
+    }
+    Ok((keeper_idx, shop))
+}
+
+fn keeper_name(keeper_idx: Index, world: &World) -> String {
+    world
+        .characters
+        .get(keeper_idx)
+        .map(|keeper| util::capitalize(keeper.formal_name()))
+        .unwrap_or_default()
+}
+
+fn char_name(char_idx: Index, world: &World) -> String {
+    world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .formal_name()
+        .to_string()
+}
+
+pub fn list(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (keeper_idx, shop) = match find_keeper(char_idx, room_id, world) {
+        Ok(found) => found,
+        Err(message) => {
+            let conn = world.connections.get_mut(conn_idx).unwrap();
+            return write!(conn, "{}\r\n", message);
+        }
+    };
+    let target = Target {
+        selection: Selection::All,
+        keyword: util::take_argument(arguments).0,
+    };
+    let keeper = world.characters.get(keeper_idx).unwrap();
+    let lines: Vec<String> = target
+        .select(keeper.inventory.iter())
+        .into_iter()
+        .map(|obj| format!("[{:5} gold] {}", shop.selling_price(obj), obj.name()))
+        .collect();
+    let name = keeper_name(keeper_idx, world);
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    if lines.is_empty() {
+        return match target.keyword {
+            Some(keyword) => write!(conn, "{} isn't selling any {}.\r\n", name, keyword),
+            None => write!(conn, "{} has nothing for sale.\r\n", name),
+        };
+    }
+    write!(conn, "{} is selling:\r\n", name)?;
+    for (count, line) in util::stack_lines(lines.iter().map(String::as_str)) {
+        write!(conn, "{}\r\n", util::stacked_line(count, line))?;
+    }
+    Ok(())
+}
+
+pub fn buy(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (keeper_idx, shop) = match find_keeper(char_idx, room_id, world) {
+        Ok(found) => found,
+        Err(message) => {
+            world.msg_char(&message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Buy what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let keeper_name = keeper_name(keeper_idx, world);
+    let char_name = char_name(char_idx, world);
+    let keeper = world.characters.get_mut(keeper_idx).unwrap();
+    let objs = util::pluck_items_from_list(&mut keeper.inventory, keyword);
+    if objs.is_empty() {
+        world.msg_char(
+            &format!("{} tells you, 'I don't sell that. Try list.'", keeper_name),
+            Recipient::Subject(char_idx),
+        );
+        return Ok(());
+    }
+
+    let mut objs = objs.into_iter();
+    while let Some(obj) = objs.next() {
+        let price = shop.selling_price(&obj);
+        let char = world.characters.get_mut(char_idx).unwrap();
+        let refusal = match char.can_carry(&obj, &[]) {
+            Err(message) => Some(message),
+            Ok(()) if !char.spend_gold(price) => Some(format!(
+                "{} tells you, 'You can't afford {}.'",
+                keeper_name,
+                obj.name()
+            )),
+            Ok(()) => None,
+        };
+        if let Some(refusal) = refusal {
+            // Whatever's left goes back on the shelf.
+            let keeper = world.characters.get_mut(keeper_idx).unwrap();
+            keeper.inventory.push_front(obj);
+            for obj in objs {
+                keeper.inventory.push_front(obj);
+            }
+            world.msg_char(&refusal, Recipient::Subject(char_idx));
+            break;
+        }
+        let you = format!("You buy {} for {} gold.", obj.name(), price);
+        let they = format!("{} buys {}.", char_name, obj.name());
+        char.inventory.push_front(obj);
+        world
+            .characters
+            .get_mut(keeper_idx)
+            .unwrap()
+            .gain_gold(price);
+        world.msg_char(&you, Recipient::Subject(char_idx));
+        world.msg_char(&they, Recipient::NotSubject(char_idx, room_id));
+    }
+    Ok(())
+}
+
+pub fn sell(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (keeper_idx, shop) = match find_keeper(char_idx, room_id, world) {
+        Ok(found) => found,
+        Err(message) => {
+            world.msg_char(&message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let keyword = match util::take_argument(arguments) {
+        (Some(keyword), _) => keyword,
+        (None, _) => {
+            world.msg_char("Sell what?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let keeper_name = keeper_name(keeper_idx, world);
+    let char_name = char_name(char_idx, world);
+    let char = world.characters.get_mut(char_idx).unwrap();
+    let objs = util::pluck_items_from_list(&mut char.inventory, keyword);
+    if objs.is_empty() {
+        world.msg_char("You aren't carrying that.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    for obj in objs {
+        let char = world.characters.get_mut(char_idx).unwrap();
+        let price = match shop.buying_price(&obj) {
+            Some(price) => price,
+            None => {
+                let refusal = format!(
+                    "{} tells you, 'I'm not interested in {}.'",
+                    keeper_name,
+                    obj.name()
+                );
+                char.inventory.push_front(obj);
+                world.msg_char(&refusal, Recipient::Subject(char_idx));
+                continue;
+            }
+        };
+        char.gain_gold(price);
+        world.msg_char(
+            &format!("You sell {} for {} gold.", obj.name(), price),
+            Recipient::Subject(char_idx),
+        );
+        world.msg_char(
+            &format!("{} sells {}.", char_name, obj.name()),
+            Recipient::NotSubject(char_idx, room_id),
+        );
+        let keeper = world.characters.get_mut(keeper_idx).unwrap();
+        keeper.inventory.push_front(obj);
+    }
+    Ok(())
+}
+
+pub fn value(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let message = find_keeper(char_idx, room_id, world).and_then(|(keeper_idx, shop)| {
+        let keyword = util::take_argument(arguments)
+            .0
+            .ok_or_else(|| String::from("Value what?"))?;
+        let char = world.characters.get(char_idx).unwrap();
+        let obj = util::find_item_by_keyword(&char.inventory, keyword)
+            .ok_or_else(|| String::from("You aren't carrying that."))?;
+        let keeper_name = keeper_name(keeper_idx, world);
+        Ok(match shop.buying_price(obj) {
+            Some(price) => format!(
+                "{} tells you, 'I'll give you {} gold for {}.'",
+                keeper_name,
+                price,
+                obj.name()
+            ),
+            None => format!(
+                "{} tells you, 'I'm not interested in {}.'",
+                keeper_name,
+                obj.name()
+            ),
+        })
+    });
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    match message {
+        Ok(message) | Err(message) => write!(conn, "{}\r\n", message),
+    }
+}
@@ -0,0 +1,166 @@
+use crate::room::{RoomFlag, RoomId};
+use crate::skill::{self, SpellTarget, Target, ADEPT, SKILLS};
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+use std::io::{Result as IoResult, Write};
+
+use super::combat::find_other;
+
+pub fn cast(conn_idx: Index, room_id: RoomId, arguments: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let (spell_name, rest) = util::take_argument(arguments);
+    let spell = match spell_name.and_then(skill::find_spell) {
+        Some(spell) if world.characters[char_idx].proficiency(spell.name) > 0 => spell,
+        Some(_) => {
+            world.msg_char("You don't know that spell.", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+        None => {
+            world.msg_char("Cast which what where?", Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    let char = &world.characters[char_idx];
+    if char.position() < spell.min_position {
+        world.msg_char(char.position().refusal(), Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    let keyword = util::take_argument(rest).0;
+    let target = match (spell.target, keyword) {
+        (Target::Offensive, _) if world.rooms[&room_id].has_flag(RoomFlag::Safe) => {
+            Err("You can't fight here.")
+        }
+        (Target::Offensive, Some(keyword)) => {
+            match find_other(char_idx, room_id, keyword, world) {
+                // Players only ever fight each other in duels.
+                Some(target_idx)
+                    if world.characters[target_idx].is_player()
+                        && char.duelling() != Some(target_idx) =>
+                {
+                    Err("Challenge them to a duel instead.")
+                }
+                Some(target_idx) => Ok(SpellTarget::Char(target_idx)),
+                None => Err("They aren't here."),
+            }
+        }
+        (Target::Offensive, None) => char
+            .fighting()
+            .map(SpellTarget::Char)
+            .ok_or("Cast the spell on whom?"),
+        (Target::Defensive, Some(keyword)) => find_other(char_idx, room_id, keyword, world)
+            .or_else(|| util::find_by_keyword(std::iter::once(char), keyword).map(|_| char_idx))
+            .map(SpellTarget::Char)
+            .ok_or("They aren't here."),
+        (Target::Defensive, None) => Ok(SpellTarget::Char(char_idx)),
+        (Target::Object, Some(keyword)) => util::find_item_by_keyword(&char.inventory, keyword)
+            .map(|obj| SpellTarget::Object(obj.instance()))
+            .ok_or("You are not carrying that."),
+        (Target::Object, None) => Err("Cast the spell on what?"),
+    };
+    let target = match target {
+        Ok(target) => target,
+        Err(message) => {
+            world.msg_char(message, Recipient::Subject(char_idx));
+            return Ok(());
+        }
+    };
+    if char.vitals().mana < spell.mana {
+        world.msg_char("You don't have enough mana.", Recipient::Subject(char_idx));
+        return Ok(());
+    }
+
+    let char = &mut world.characters[char_idx];
+    char.add_lag(spell.lag);
+    let succeeded = util::random_percent() <= char.proficiency(spell.name);
+    // A botched spell still costs half the mana.
+    char.vitals_mut().mana -= if succeeded {
+        spell.mana
+    } else {
+        spell.mana / 2
+    };
+    let improved = char.improve_skill(spell.name, succeeded);
+    if succeeded {
+        spell.cast(char_idx, target, world);
+    } else {
+        world.msg_char("You lost your concentration.", Recipient::Subject(char_idx));
+    }
+    if improved && world.characters.contains(char_idx) {
+        world.msg_char(
+            &format!("You have become better at {}!", spell.name),
+            Recipient::Subject(char_idx),
+        );
+    }
+    Ok(())
+}
+
+pub fn practice(
+    conn_idx: Index,
+    _room_id: RoomId,
+    arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let char = world.characters.get_mut(conn.character).unwrap();
+    let name = arguments.trim();
+    if name.is_empty() {
+        for skill in SKILLS {
+            write!(
+                conn,
+                "{:<18}{:>3}%\r\n",
+                skill.name,
+                char.proficiency(skill.name)
+            )?;
+        }
+        return write!(
+            conn,
+            "You have {} practice sessions left.\r\n",
+            char.practices()
+        );
+    }
+    let skill = match skill::find(name) {
+        Some(skill) => skill,
+        None => return write!(conn, "You can't practice that.\r\n"),
+    };
+    let learned = char.proficiency(skill.name);
+    if learned >= ADEPT {
+        return write!(conn, "You are already learned at {}.\r\n", skill.name);
+    }
+    if !char.spend_practice() {
+        return write!(conn, "You have no practice sessions left.\r\n");
+    }
+    let learned = (learned + skill::practice_gain(char.attributes().intelligence)).min(ADEPT);
+    char.set_proficiency(skill.name, learned);
+    if learned >= ADEPT {
+        write!(conn, "You are now learned at {}.\r\n", skill.name)
+    } else {
+        write!(conn, "You practice {}.\r\n", skill.name)
+    }
+}
+
+pub fn slist(
+    conn_idx: Index,
+    _room_id: RoomId,
+    _arguments: &str,
+    world: &mut World,
+) -> IoResult<()> {
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    let char = world.characters.get(conn.character).unwrap();
+    write!(conn, "Name               Mana  Target    Known\r\n")?;
+    for skill in SKILLS {
+        let mana = match skill.spell {
+            Some(_) => skill.mana.to_string(),
+            None => String::from("-"),
+        };
+        write!(
+            conn,
+            "{:<18}{:>5}  {:<10}{}%\r\n",
+            skill.name,
+            mana,
+            format!("{:?}", skill.target),
+            char.proficiency(skill.name)
+        )?;
+    }
+    Ok(())
+}
@@ -3,6 +3,8 @@ use std::io::{prelude::*, ErrorKind, Result as IoResult, Write};
 use std::net::{SocketAddr, TcpStream};
 
 use crate::character::Player;
+use crate::object::Writing;
+use crate::olc::Editor;
 use serde::export::fmt::Arguments;
 
 pub struct ConnectionBuilder {
@@ -24,6 +26,8 @@ impl ConnectionBuilder {
             in_buffer: [0; 256],
             input: None,
             output: vec![],
+            editor: None,
+            writing: None,
         }
     }
 }
@@ -36,6 +40,8 @@ pub struct Connection {
     in_buffer: [u8; 256],
     pub input: Option<String>, // TODO: do this better
     output: Vec<u8>,
+    pub editor: Option<Editor>,
+    pub writing: Option<Writing>,
 }
 
 impl Connection {
@@ -47,6 +53,10 @@ impl Connection {
         &self.player
     }
 
+    pub fn player_mut(&mut self) -> &mut Player {
+        &mut self.player
+    }
+
     pub fn player_name(&self) -> &str {
         self.player.name()
     }
@@ -1,21 +1,27 @@
 mod area;
 mod character;
+mod combat;
 pub mod commands;
 mod connection;
 mod listener;
 mod object;
+mod olc;
 mod room;
+mod skill;
+mod time;
 pub mod util;
+mod weather;
 pub mod world;
 
-pub use area::Area;
+pub use area::{lint, merc, Area, AreaDef, AreaLoadError, AreaSaveError};
 pub use character::{CharId, Character, PlayerRecord};
 pub use commands::lookup_command;
 pub use connection::{Connection, ConnectionBuilder};
 pub use listener::listen;
 pub use object::{
-    AllObjectsAdapter, Object, ObjectDef, ObjectId, ObjectInRoomAdapter, ObjectOnCharAdapter,
-    ObjectType,
+    AllObjectsAdapter, Container, EquipSlot, InstanceId, Object, ObjectDef, ObjectId,
+    ObjectInObjectAdapter, ObjectInRoomAdapter, ObjectOnCharAdapter, ObjectType, WearLocation,
 };
-pub use room::{Exit, Room, RoomId};
+pub use room::{Exit, Room, RoomFlag, RoomId, SectorType};
+pub use time::GameTime;
 pub use world::World;
@@ -71,10 +71,15 @@ async fn load_old_character(name: &str) -> Result<Option<PlayerRecord>, LoadErro
             f.read_to_end(&mut v)
                 .await
                 .map_err(|e| LoadError::IO(e, name.to_string()))?;
-            Ok(Some(serde_json::de::from_slice(&v).map_err(|e| {
+            let record: PlayerRecord = serde_json::de::from_slice(&v).map_err(|e| {
                 log::error!("Load error {}", e);
                 LoadError::Unparsable(name.to_string())
-            })?))
+            })?;
+            record.check_objects().map_err(|e| {
+                log::error!("Load error in {}'s objects: {}", name, e);
+                LoadError::Unparsable(name.to_string())
+            })?;
+            Ok(Some(record))
         }
     }
 }
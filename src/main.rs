@@ -5,19 +5,30 @@ use std::io::Write;
 use std::net::TcpListener;
 use std::rc::Rc;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use fennel::{listen, util, Character, ConnectionBuilder, PlayerRecord, RoomId, World};
+use fennel::{listen, util, Character, ConnectionBuilder, GameTime, PlayerRecord, RoomId, World};
 
 static PULSE_PER_SECOND: u32 = 3;
 static PULSE_RATE_NS: u32 = 1_000_000_000 / PULSE_PER_SECOND;
+// One game hour
+static PULSE_PER_TICK: u32 = 30 * PULSE_PER_SECOND;
+// One round of combat
+static PULSE_PER_VIOLENCE: u32 = 3 * PULSE_PER_SECOND;
 
 fn accept_new_connections(
     world: &mut World,
     receiver: &Receiver<(ConnectionBuilder, PlayerRecord)>,
 ) {
     while let Ok((conn_builder, record)) = receiver.try_recv() {
-        let (player, char_data, inventory) = record.into_inner();
+        let (player, char_data, (inventory, equipment)) = match record.into_inner() {
+            Ok(parts) => parts,
+            Err(e) => {
+                // The login thread already checked, so this shouldn't happen.
+                log::error!("Couldn't read objects from {}: {}", conn_builder.addr, e);
+                continue;
+            }
+        };
 
         let conn = if let Some((conn_index, _existing_conn)) = world
             .connections
@@ -60,8 +71,15 @@ fn accept_new_connections(
             for obj in inventory {
                 let obj = Rc::new(obj);
                 world.objects.push_back(Rc::clone(&obj));
+                obj.for_each_content(&mut |inner| world.objects.push_back(Rc::clone(inner)));
                 char.inventory.push_back(obj);
             }
+            for (slot, obj) in equipment {
+                let obj = Rc::new(obj);
+                world.objects.push_back(Rc::clone(&obj));
+                obj.for_each_content(&mut |inner| world.objects.push_back(Rc::clone(inner)));
+                char.equipment.equip(slot, obj);
+            }
 
             // TODO: use world.char_to_room here for consistency
             let in_room = world
@@ -93,27 +111,53 @@ fn game_loop(
     connection_receiver: Receiver<(ConnectionBuilder, PlayerRecord)>,
 ) -> std::io::Result<()> {
     let mut last_time: Instant;
+    let mut pulse_until_tick = PULSE_PER_TICK;
+    let mut pulse_until_violence = PULSE_PER_VIOLENCE;
 
     let mut world = World::new();
 
     world.populate();
 
+    // The calendar picks up where it would be if the game had been running all along.
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let hours = seconds * u64::from(PULSE_PER_SECOND) / u64::from(PULSE_PER_TICK);
+    world.start_clock(GameTime::from_hours(hours));
+
     loop {
         last_time = Instant::now();
 
         accept_new_connections(&mut world, &connection_receiver);
 
-        // TODO: decrement lag here
+        world.decrement_lag();
 
         world.read_input();
 
         world.run_player_commands();
 
+        pulse_until_violence -= 1;
+        if pulse_until_violence == 0 {
+            pulse_until_violence = PULSE_PER_VIOLENCE;
+            world.violence();
+        }
+
+        pulse_until_tick -= 1;
+        if pulse_until_tick == 0 {
+            pulse_until_tick = PULSE_PER_TICK;
+            world.tick();
+        }
+
         // handle output
         for (_idx, conn) in &mut world.connections {
-            let _ = conn.write_flush(Some(
-                "You are who you are; You are where you are; The time is now>",
-            ));
+            let prompt = match (&conn.editor, &conn.writing) {
+                (_, Some(_)) => String::from("] "),
+                (Some(editor), None) => editor.prompt(),
+                (None, None) => {
+                    world.characters[conn.character].prompt(&conn.player().preferences().prompt)
+                }
+            };
+            let _ = conn.write_flush(Some(&prompt));
         }
 
         let now = Instant::now();
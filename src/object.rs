@@ -1,56 +1,266 @@
-use crate::util::HasKeywords;
-use intrusive_collections::{intrusive_adapter, LinkedListLink};
+mod book;
+mod consumable;
+mod container;
+mod furniture;
+mod portal;
+mod wear;
+
+use crate::character::{Effect, EffectKind, Effects};
+use crate::util::{self, HasKeywords};
+use intrusive_collections::{intrusive_adapter, LinkedList, LinkedListLink};
 use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
 use std::default::Default;
+use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 
+pub use book::{Book, Page, Writing, MAX_PAGE_LENGTH};
+pub use consumable::{Drink, Food, Liquid, Medicine};
+pub use container::Container;
+pub use furniture::Furniture;
+pub use portal::Portal;
+pub use wear::{EquipSlot, WearLocation};
+
 #[derive(Copy, Clone, Debug, Default, Deserialize, Hash, Eq, PartialEq, Serialize)]
 pub struct ObjectId(usize);
 
+impl From<usize> for ObjectId {
+    fn from(id: usize) -> ObjectId {
+        ObjectId(id)
+    }
+}
+
+impl Display for ObjectId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Tells apart objects made from the same definition. These are random, so they
+// stay unique across reboots and player files without any bookkeeping.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Hash, Eq, PartialEq, Serialize)]
+pub struct InstanceId(u64);
+
+impl InstanceId {
+    pub fn new() -> InstanceId {
+        InstanceId(util::random_u64())
+    }
+}
+
+impl Display for InstanceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Default, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct ObjectDef {
     pub id: ObjectId,
-    keywords: Vec<String>,
-    name: String,
-    room_description: String,
-    description: Option<String>,
-    object_type: ObjectType,
+    pub(crate) keywords: Vec<String>,
+    pub(crate) name: String,
+    pub(crate) room_description: String,
+    pub(crate) description: Option<String>,
+    pub(crate) object_type: ObjectType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) wear: Option<WearLocation>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub(crate) weight: u32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub(crate) value: u32,
+    // How many hours a light source burns for. Lights without it never go out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) light_hours: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) container: Option<Container>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) food: Option<Food>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) drink: Option<Drink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) medicine: Option<Medicine>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) book: Option<Book>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) portal: Option<Portal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) furniture: Option<Furniture>,
 }
 
 intrusive_adapter!(pub ObjectInRoomAdapter = Rc<Object>: Object { in_room_link: LinkedListLink });
 intrusive_adapter!(pub ObjectOnCharAdapter = Rc<Object>: Object { on_char_link: LinkedListLink });
 intrusive_adapter!(pub AllObjectsAdapter = Rc<Object>: Object { all_objs_link: LinkedListLink });
+intrusive_adapter!(pub ObjectInObjectAdapter = Rc<Object>: Object { in_obj_link: LinkedListLink });
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+fn never_decays(decay: &Cell<Option<u32>>) -> bool {
+    decay.get().is_none()
+}
+
+fn no_effects(effects: &RefCell<Effects>) -> bool {
+    effects.borrow().is_empty()
+}
+
+// How long a corpse lasts before it rots, in game hours.
+const CORPSE_HOURS: u32 = 12;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Object {
     id: ObjectId,
+    // Objects saved before instances existed get a fresh id when they're loaded.
+    #[serde(default = "InstanceId::new")]
+    instance: InstanceId,
     keywords: Vec<String>,
     name: String,
     room_description: String,
     description: Option<String>,
     object_type: ObjectType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wear: Option<WearLocation>,
+    #[serde(default)]
+    weight: u32,
+    #[serde(default)]
+    value: u32,
+    #[serde(default)]
+    light_hours: Cell<Option<u32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    container: Option<Container>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    food: Option<Food>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    drink: Option<Drink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    medicine: Option<Medicine>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    book: Option<Book>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    portal: Option<Portal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    furniture: Option<Furniture>,
+    // How many more game hours until it rots away. Only corpses do, for now.
+    #[serde(default, skip_serializing_if = "never_decays")]
+    decay: Cell<Option<u32>>,
+    #[serde(default, skip_serializing_if = "no_effects")]
+    effects: RefCell<Effects>,
+    #[serde(
+        default,
+        skip_serializing_if = "container::is_empty",
+        with = "container::contents"
+    )]
+    pub contents: RefCell<LinkedList<ObjectInObjectAdapter>>,
     #[serde(skip)]
     in_room_link: LinkedListLink,
     #[serde(skip)]
     on_char_link: LinkedListLink,
     #[serde(skip)]
     all_objs_link: LinkedListLink,
+    #[serde(skip)]
+    in_obj_link: LinkedListLink,
+}
+
+// Cloning an object clones everything inside it, too. A clone is the same
+// object, instance id and all, so it's for saving, not for duplicating.
+impl Clone for Object {
+    fn clone(&self) -> Object {
+        let mut contents = LinkedList::new(ObjectInObjectAdapter::new());
+        for obj in self.contents.borrow().iter() {
+            contents.push_back(Rc::new(obj.clone()));
+        }
+        Object {
+            id: self.id,
+            instance: self.instance,
+            keywords: self.keywords.clone(),
+            name: self.name.clone(),
+            room_description: self.room_description.clone(),
+            description: self.description.clone(),
+            object_type: self.object_type,
+            wear: self.wear,
+            weight: self.weight,
+            value: self.value,
+            light_hours: self.light_hours.clone(),
+            container: self.container.clone(),
+            food: self.food.clone(),
+            drink: self.drink.clone(),
+            medicine: self.medicine.clone(),
+            book: self.book.clone(),
+            portal: self.portal.clone(),
+            furniture: self.furniture.clone(),
+            decay: self.decay.clone(),
+            effects: self.effects.clone(),
+            contents: RefCell::new(contents),
+            ..Default::default()
+        }
+    }
 }
 
 impl Object {
     pub fn from_prototype(def: &ObjectDef) -> Object {
         Object {
             id: def.id,
+            instance: InstanceId::new(),
             keywords: def.keywords.clone(),
             name: def.name.clone(),
             room_description: def.room_description.clone(),
             description: def.description.clone(),
             object_type: def.object_type,
+            wear: def.wear,
+            weight: def.weight,
+            value: def.value,
+            light_hours: Cell::new(def.light_hours),
+            container: def.container.clone(),
+            food: def.food.clone(),
+            drink: def.drink.clone(),
+            medicine: def.medicine.clone(),
+            book: def.book.clone(),
+            portal: def.portal.clone(),
+            furniture: def.furniture.clone(),
             ..Default::default()
         }
     }
 
+    /// What's left of someone after they die. It holds whatever they had on them
+    /// and rots away after a while.
+    pub fn corpse(name: &str, keywords: &[String]) -> Object {
+        let mut corpse_keywords = vec![String::from("corpse")];
+        corpse_keywords.extend(keywords.iter().cloned());
+        Object {
+            instance: InstanceId::new(),
+            keywords: corpse_keywords,
+            name: format!("the corpse of {}", name),
+            room_description: format!("The corpse of {} is lying here.", name),
+            object_type: ObjectType::Container,
+            weight: 100,
+            container: Some(Container {
+                capacity: 100,
+                ..Default::default()
+            }),
+            decay: Cell::new(Some(CORPSE_HOURS)),
+            ..Default::default()
+        }
+    }
+
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    pub fn instance(&self) -> InstanceId {
+        self.instance
+    }
+
+    /// How heavy this is, counting everything inside it.
+    pub fn weight(&self) -> u32 {
+        let mut weight = self.weight;
+        self.for_each_content(&mut |obj| weight += obj.weight);
+        weight
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -62,6 +272,98 @@ impl Object {
     pub fn description(&self) -> &str {
         self.description.as_ref().unwrap_or(&self.room_description)
     }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    pub fn wear_location(&self) -> Option<WearLocation> {
+        self.wear
+    }
+
+    pub fn container(&self) -> Option<&Container> {
+        self.container.as_ref()
+    }
+
+    pub fn food(&self) -> Option<&Food> {
+        self.food.as_ref()
+    }
+
+    pub fn drink(&self) -> Option<&Drink> {
+        self.drink.as_ref()
+    }
+
+    pub fn medicine(&self) -> Option<&Medicine> {
+        self.medicine.as_ref()
+    }
+
+    pub fn book(&self) -> Option<&Book> {
+        self.book.as_ref()
+    }
+
+    pub fn portal(&self) -> Option<&Portal> {
+        self.portal.as_ref()
+    }
+
+    pub fn furniture(&self) -> Option<&Furniture> {
+        self.furniture.as_ref()
+    }
+
+    /// Whether this is still counted among the world's objects.
+    pub(crate) fn is_in_world(&self) -> bool {
+        self.all_objs_link.is_linked()
+    }
+
+    /// Calls `f` on everything inside this object, however deeply it's nested.
+    pub fn for_each_content<F: FnMut(&Rc<Object>)>(&self, f: &mut F) {
+        let contents = self.contents.borrow();
+        let mut cursor = contents.front();
+        while let Some(obj) = cursor.clone_pointer() {
+            f(&obj);
+            obj.for_each_content(f);
+            cursor.move_next();
+        }
+    }
+
+    /// Counts down an hour. Returns whether the object has rotted away.
+    pub fn rot(&self) -> bool {
+        match self.decay.get() {
+            Some(hours) => {
+                let hours = hours.saturating_sub(1);
+                self.decay.set(Some(hours));
+                hours == 0
+            }
+            None => false,
+        }
+    }
+
+    pub fn has_effect(&self, kind: EffectKind) -> bool {
+        self.effects.borrow().has(kind)
+    }
+
+    pub fn add_effect(&self, effect: Effect) {
+        self.effects.borrow_mut().add(effect)
+    }
+
+    /// Counts down an hour on every effect, returning the ones that wore off.
+    pub fn tick_effects(&self) -> Vec<EffectKind> {
+        self.effects.borrow_mut().tick()
+    }
+
+    pub fn is_lit(&self) -> bool {
+        self.object_type == ObjectType::Light && self.light_hours.get() != Some(0)
+    }
+
+    /// Burns a light down by an hour. Returns true if that put it out.
+    pub fn burn(&self) -> bool {
+        match self.light_hours.get() {
+            Some(hours) if hours > 0 && self.object_type == ObjectType::Light => {
+                self.light_hours.set(Some(hours - 1));
+                hours == 1
+            }
+            _ => false,
+        }
+    }
 }
 
 impl HasKeywords for Object {
@@ -87,6 +389,37 @@ pub enum ObjectType {
     Portal,
     Book,
     Art,
+    Container,
+    Furniture,
+}
+
+impl ObjectType {
+    pub const ALL: &'static [ObjectType] = &[
+        ObjectType::Trash,
+        ObjectType::Light,
+        ObjectType::Clothing,
+        ObjectType::Jewelry,
+        ObjectType::Armor,
+        ObjectType::Weapon,
+        ObjectType::Treasure,
+        ObjectType::Food,
+        ObjectType::Drink,
+        ObjectType::Medicine,
+        ObjectType::Plant,
+        ObjectType::Environment,
+        ObjectType::Portal,
+        ObjectType::Book,
+        ObjectType::Art,
+        ObjectType::Container,
+        ObjectType::Furniture,
+    ];
+
+    pub fn parse(name: &str) -> Option<ObjectType> {
+        ObjectType::ALL
+            .iter()
+            .find(|ty| format!("{:?}", ty).eq_ignore_ascii_case(name))
+            .copied()
+    }
 }
 
 impl Default for ObjectType {
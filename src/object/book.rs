@@ -0,0 +1,76 @@
+use super::InstanceId;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+// Journals fill up eventually.
+pub const MAX_PAGES: usize = 50;
+
+// And so does each page, in letters.
+pub const MAX_PAGE_LENGTH: usize = 4000;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Page {
+    pub title: String,
+    pub text: String,
+}
+
+// Pages are kept per instance, so that a journal remembers what its owner wrote.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Book {
+    #[serde(default, skip_serializing_if = "no_pages")]
+    pub pages: RefCell<Vec<Page>>,
+    // Whether players can add pages of their own
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub writable: bool,
+}
+
+fn no_pages(pages: &RefCell<Vec<Page>>) -> bool {
+    pages.borrow().is_empty()
+}
+
+impl Book {
+    pub fn page_count(&self) -> usize {
+        self.pages.borrow().len()
+    }
+
+    /// Looks up a page by its number, counting from 1.
+    pub fn page(&self, number: usize) -> Option<Page> {
+        number
+            .checked_sub(1)
+            .and_then(|idx| self.pages.borrow().get(idx).cloned())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.page_count() >= MAX_PAGES
+    }
+}
+
+// A page a player is in the middle of writing. Their input goes here, a line at a
+// time, until they end it with "@".
+#[derive(Debug)]
+pub struct Writing {
+    pub book: InstanceId,
+    pub title: String,
+    pub text: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Book, Page};
+    use std::cell::RefCell;
+
+    #[test]
+    fn pages_count_from_one() {
+        let book = Book {
+            pages: RefCell::new(vec![Page {
+                title: String::from("Preface"),
+                text: String::from("Hello.\n"),
+            }]),
+            writable: false,
+        };
+        assert_eq!(book.page(0), None);
+        assert_eq!(book.page(1).unwrap().title, "Preface");
+        assert_eq!(book.page(2), None);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::character::{Effect, EffectKind};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+// The same liquids as Merc, minus the drunkenness.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Liquid {
+    #[default]
+    Water,
+    Beer,
+    Wine,
+    Ale,
+    DarkAle,
+    Whisky,
+    Lemonade,
+    Firebreather,
+    LocalSpecialty,
+    SlimeMoldJuice,
+    Milk,
+    Tea,
+    Coffee,
+    Blood,
+    SaltWater,
+    Cola,
+}
+
+impl Liquid {
+    // In Merc's order, so its liquid numbers can be used as indexes.
+    pub const ALL: &'static [Liquid] = &[
+        Liquid::Water,
+        Liquid::Beer,
+        Liquid::Wine,
+        Liquid::Ale,
+        Liquid::DarkAle,
+        Liquid::Whisky,
+        Liquid::Lemonade,
+        Liquid::Firebreather,
+        Liquid::LocalSpecialty,
+        Liquid::SlimeMoldJuice,
+        Liquid::Milk,
+        Liquid::Tea,
+        Liquid::Coffee,
+        Liquid::Blood,
+        Liquid::SaltWater,
+        Liquid::Cola,
+    ];
+
+    pub fn parse(name: &str) -> Option<Liquid> {
+        Liquid::ALL
+            .iter()
+            .find(|liquid| format!("{:?}", liquid).eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Liquid::Water => "water",
+            Liquid::Beer => "beer",
+            Liquid::Wine => "wine",
+            Liquid::Ale => "ale",
+            Liquid::DarkAle => "dark ale",
+            Liquid::Whisky => "whisky",
+            Liquid::Lemonade => "lemonade",
+            Liquid::Firebreather => "firebreather",
+            Liquid::LocalSpecialty => "local specialty",
+            Liquid::SlimeMoldJuice => "slime mold juice",
+            Liquid::Milk => "milk",
+            Liquid::Tea => "tea",
+            Liquid::Coffee => "coffee",
+            Liquid::Blood => "blood",
+            Liquid::SaltWater => "salt water",
+            Liquid::Cola => "cola",
+        }
+    }
+
+    /// How much a sip fills you up and quenches your thirst.
+    pub fn nourishment(&self) -> (i32, i32) {
+        match self {
+            Liquid::Water => (0, 10),
+            Liquid::Beer => (2, 5),
+            Liquid::Wine => (2, 5),
+            Liquid::Ale => (2, 5),
+            Liquid::DarkAle => (2, 5),
+            Liquid::Whisky => (1, 4),
+            Liquid::Lemonade => (1, 8),
+            Liquid::Firebreather => (0, 0),
+            Liquid::LocalSpecialty => (3, 3),
+            Liquid::SlimeMoldJuice => (4, -8),
+            Liquid::Milk => (3, 6),
+            Liquid::Tea => (1, 6),
+            Liquid::Coffee => (1, 6),
+            Liquid::Blood => (2, -1),
+            Liquid::SaltWater => (1, -2),
+            Liquid::Cola => (1, 5),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Food {
+    // How many hours of hunger a meal is worth
+    pub hours: u32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub poisoned: bool,
+}
+
+// Something to drink out of. Fountains never run dry and can't be carried off
+// to refill anywhere else.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Drink {
+    pub liquid: Cell<Liquid>,
+    #[serde(default)]
+    pub capacity: u32,
+    #[serde(default)]
+    pub amount: Cell<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fountain: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub poisoned: Cell<bool>,
+}
+
+fn is_false(flag: &Cell<bool>) -> bool {
+    !flag.get()
+}
+
+impl Drink {
+    pub fn is_empty(&self) -> bool {
+        !self.fountain && self.amount.get() == 0
+    }
+
+    /// Takes a sip, returning what was in it.
+    pub fn sip(&self) -> Liquid {
+        if !self.fountain {
+            self.amount.set(self.amount.get().saturating_sub(1));
+            if self.amount.get() == 0 {
+                self.poisoned.set(false);
+            }
+        }
+        self.liquid.get()
+    }
+}
+
+// Pills and potions. Curing happens right away; effects last a while.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Medicine {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cures: Vec<EffectKind>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<Effect>,
+}
@@ -0,0 +1,96 @@
+use super::{ObjectId, ObjectInObjectAdapter};
+use crate::room::Door;
+use intrusive_collections::LinkedList;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+
+// A container opens, closes, and locks just like a door does, so it keeps its
+// lid in a `Door`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Container {
+    // How many things fit inside
+    pub capacity: usize,
+    #[serde(default, skip_serializing_if = "no_lid")]
+    pub lid: Cell<Door>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<ObjectId>,
+}
+
+fn no_lid(lid: &Cell<Door>) -> bool {
+    !lid.get().is_present()
+}
+
+impl Container {
+    pub fn is_closed(&self) -> bool {
+        self.lid.get().is_closed()
+    }
+}
+
+// Contents are saved along with the object that holds them, as a plain list.
+pub(super) mod contents {
+    use crate::object::{Object, ObjectInObjectAdapter};
+    use intrusive_collections::LinkedList;
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    pub fn serialize<S: Serializer>(
+        contents: &RefCell<LinkedList<ObjectInObjectAdapter>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let contents = contents.borrow();
+        let mut seq = serializer.serialize_seq(None)?;
+        for obj in contents.iter() {
+            seq.serialize_element(obj)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RefCell<LinkedList<ObjectInObjectAdapter>>, D::Error> {
+        let objects = Vec::<Object>::deserialize(deserializer)?;
+        let mut contents = LinkedList::new(ObjectInObjectAdapter::new());
+        for obj in objects {
+            contents.push_back(Rc::new(obj));
+        }
+        Ok(RefCell::new(contents))
+    }
+}
+
+pub(super) fn is_empty(contents: &RefCell<LinkedList<ObjectInObjectAdapter>>) -> bool {
+    contents.borrow().is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::object::{Object, ObjectDef, ObjectId};
+    use std::rc::Rc;
+
+    #[test]
+    fn contents_round_trip() {
+        let def = |id: usize| ObjectDef {
+            id: ObjectId::from(id),
+            ..Default::default()
+        };
+        let bag = Object::from_prototype(&def(1));
+        let pouch = Object::from_prototype(&def(2));
+        pouch
+            .contents
+            .borrow_mut()
+            .push_back(Rc::new(Object::from_prototype(&def(3))));
+        bag.contents.borrow_mut().push_back(Rc::new(pouch));
+
+        let json = serde_json::to_string(&bag.clone()).unwrap();
+        let loaded: Object = serde_json::from_str(&json).unwrap();
+        let contents = loaded.contents.borrow();
+        let pouch = contents.front().get().unwrap();
+        assert_eq!(pouch.id(), ObjectId::from(2));
+        assert_eq!(
+            pouch.contents.borrow().front().get().map(|obj| obj.id()),
+            Some(ObjectId::from(3))
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::character::Position;
+use serde::{Deserialize, Serialize};
+
+// Something to sit, rest, or sleep on, with room for a few people at once.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Furniture {
+    pub seats: usize,
+    pub allows: Vec<Position>,
+}
+
+impl Default for Furniture {
+    fn default() -> Furniture {
+        Furniture {
+            seats: 1,
+            allows: vec![Position::Sitting, Position::Resting],
+        }
+    }
+}
+
+impl Furniture {
+    pub fn allows(&self, position: Position) -> bool {
+        self.allows.contains(&position)
+    }
+}
@@ -0,0 +1,71 @@
+use super::ObjectId;
+use crate::room::RoomId;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+// A way from one room to somewhere else that isn't an exit. It goes to a fixed
+// room, a random room in an area, or wherever its paired portal is, which is
+// how two-way portals are made. Charges and decay both count down to the
+// portal fading away.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Portal {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<RoomId>,
+    // The file name of an area, as used by areareload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_area: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<ObjectId>,
+    // How many more times it can be entered
+    #[serde(default, skip_serializing_if = "is_unlimited")]
+    pub charges: Cell<Option<u32>>,
+    // How many more game hours it lasts
+    #[serde(default, skip_serializing_if = "is_unlimited")]
+    pub decay: Cell<Option<u32>>,
+}
+
+fn is_unlimited(count: &Cell<Option<u32>>) -> bool {
+    count.get().is_none()
+}
+
+impl Portal {
+    /// Uses up a charge. Returns whether that was the last one.
+    pub fn spend_charge(&self) -> bool {
+        spend(&self.charges)
+    }
+
+    /// Counts down an hour. Returns whether the portal has run out of time.
+    pub fn decay(&self) -> bool {
+        spend(&self.decay)
+    }
+}
+
+fn spend(count: &Cell<Option<u32>>) -> bool {
+    match count.get() {
+        Some(left) => {
+            let left = left.saturating_sub(1);
+            count.set(Some(left));
+            left == 0
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Portal;
+    use std::cell::Cell;
+
+    #[test]
+    fn charges_run_out() {
+        let portal = Portal {
+            charges: Cell::new(Some(2)),
+            ..Default::default()
+        };
+        assert!(!portal.spend_charge());
+        assert!(portal.spend_charge());
+        assert!(!Portal::default().spend_charge());
+        assert!(!Portal::default().decay());
+    }
+}
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+// Where an object can be worn. Some locations have room for two objects, so
+// each location maps onto one or more equipment slots on a character.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WearLocation {
+    Light,
+    Finger,
+    Neck,
+    Body,
+    Head,
+    Legs,
+    Feet,
+    Hands,
+    Arms,
+    Shield,
+    About,
+    Waist,
+    Wrist,
+    Wield,
+    Hold,
+}
+
+impl WearLocation {
+    pub const ALL: &'static [WearLocation] = &[
+        WearLocation::Light,
+        WearLocation::Finger,
+        WearLocation::Neck,
+        WearLocation::Body,
+        WearLocation::Head,
+        WearLocation::Legs,
+        WearLocation::Feet,
+        WearLocation::Hands,
+        WearLocation::Arms,
+        WearLocation::Shield,
+        WearLocation::About,
+        WearLocation::Waist,
+        WearLocation::Wrist,
+        WearLocation::Wield,
+        WearLocation::Hold,
+    ];
+
+    pub fn parse(name: &str) -> Option<WearLocation> {
+        WearLocation::ALL
+            .iter()
+            .find(|location| format!("{:?}", location).eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub fn slots(&self) -> &'static [EquipSlot] {
+        match self {
+            WearLocation::Light => &[EquipSlot::Light],
+            WearLocation::Finger => &[EquipSlot::FingerLeft, EquipSlot::FingerRight],
+            WearLocation::Neck => &[EquipSlot::NeckInner, EquipSlot::NeckOuter],
+            WearLocation::Body => &[EquipSlot::Body],
+            WearLocation::Head => &[EquipSlot::Head],
+            WearLocation::Legs => &[EquipSlot::Legs],
+            WearLocation::Feet => &[EquipSlot::Feet],
+            WearLocation::Hands => &[EquipSlot::Hands],
+            WearLocation::Arms => &[EquipSlot::Arms],
+            WearLocation::Shield => &[EquipSlot::Shield],
+            WearLocation::About => &[EquipSlot::About],
+            WearLocation::Waist => &[EquipSlot::Waist],
+            WearLocation::Wrist => &[EquipSlot::WristLeft, EquipSlot::WristRight],
+            WearLocation::Wield => &[EquipSlot::Wield],
+            WearLocation::Hold => &[EquipSlot::Hold],
+        }
+    }
+}
+
+// A place on a character that holds one object. Ordered head to toe, the way
+// equipment is listed.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum EquipSlot {
+    Light,
+    FingerLeft,
+    FingerRight,
+    NeckInner,
+    NeckOuter,
+    Body,
+    Head,
+    Legs,
+    Feet,
+    Hands,
+    Arms,
+    Shield,
+    About,
+    Waist,
+    WristLeft,
+    WristRight,
+    Wield,
+    Hold,
+}
+
+impl EquipSlot {
+    /// The label shown next to an object in this slot, padded to line up.
+    pub fn label(&self) -> &'static str {
+        match self {
+            EquipSlot::Light => "<used as light>     ",
+            EquipSlot::FingerLeft | EquipSlot::FingerRight => "<worn on finger>    ",
+            EquipSlot::NeckInner | EquipSlot::NeckOuter => "<worn around neck>  ",
+            EquipSlot::Body => "<worn on body>      ",
+            EquipSlot::Head => "<worn on head>      ",
+            EquipSlot::Legs => "<worn on legs>      ",
+            EquipSlot::Feet => "<worn on feet>      ",
+            EquipSlot::Hands => "<worn on hands>     ",
+            EquipSlot::Arms => "<worn on arms>      ",
+            EquipSlot::Shield => "<worn as shield>    ",
+            EquipSlot::About => "<worn about body>   ",
+            EquipSlot::Waist => "<worn about waist>  ",
+            EquipSlot::WristLeft | EquipSlot::WristRight => "<worn around wrist> ",
+            EquipSlot::Wield => "<wielded>           ",
+            EquipSlot::Hold => "<held>              ",
+        }
+    }
+
+    /// Where on a body this slot is, for messages like "on her head".
+    pub fn body_part(&self, possessive: &str) -> String {
+        match self {
+            EquipSlot::Light | EquipSlot::Wield | EquipSlot::Hold => {
+                format!("in {} hand", possessive)
+            }
+            EquipSlot::FingerLeft => format!("on {} left finger", possessive),
+            EquipSlot::FingerRight => format!("on {} right finger", possessive),
+            EquipSlot::NeckInner | EquipSlot::NeckOuter => format!("around {} neck", possessive),
+            EquipSlot::Body => format!("on {} body", possessive),
+            EquipSlot::Head => format!("on {} head", possessive),
+            EquipSlot::Legs => format!("on {} legs", possessive),
+            EquipSlot::Feet => format!("on {} feet", possessive),
+            EquipSlot::Hands => format!("on {} hands", possessive),
+            EquipSlot::Arms => format!("on {} arms", possessive),
+            EquipSlot::Shield => String::from("as a shield"),
+            EquipSlot::About => format!("about {} body", possessive),
+            EquipSlot::Waist => format!("about {} waist", possessive),
+            EquipSlot::WristLeft => format!("around {} left wrist", possessive),
+            EquipSlot::WristRight => format!("around {} right wrist", possessive),
+        }
+    }
+}
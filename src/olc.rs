@@ -0,0 +1,65 @@
+use crate::character::CharId;
+use crate::object::ObjectId;
+use crate::room::RoomId;
+
+// Online creation: the state of a builder's editing session. While a
+// connection has an `Editor`, its input goes to the editor instead of the
+// command table.
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EditTarget {
+    Room(RoomId),
+    Object(ObjectId),
+    Npc(CharId),
+}
+
+// Which multi-line field a text buffer will be stored into.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TextField {
+    Description,
+    NightDescription,
+    ExtraDescription(Vec<String>),
+    Page(String),
+}
+
+// Collects the lines of a multi-line description until the builder ends it with "@"
+#[derive(Debug)]
+pub struct TextBuffer {
+    pub field: TextField,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct Editor {
+    pub target: EditTarget,
+    pub area: usize,
+    pub text: Option<TextBuffer>,
+}
+
+impl Editor {
+    pub fn new(target: EditTarget, area: usize) -> Editor {
+        Editor {
+            target,
+            area,
+            text: None,
+        }
+    }
+
+    pub fn start_text(&mut self, field: TextField) {
+        self.text = Some(TextBuffer {
+            field,
+            text: String::new(),
+        });
+    }
+
+    pub fn prompt(&self) -> String {
+        if self.text.is_some() {
+            return String::from("] ");
+        }
+        match self.target {
+            EditTarget::Room(id) => format!("Editing room {}>", id),
+            EditTarget::Object(id) => format!("Editing object {}>", id),
+            EditTarget::Npc(id) => format!("Editing npc {}>", id),
+        }
+    }
+}
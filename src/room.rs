@@ -1,6 +1,7 @@
 mod direction;
 mod door;
 mod exit;
+mod flags;
 
 use crate::object::ObjectId;
 use serde::{Deserialize, Serialize};
@@ -8,10 +9,11 @@ use std::default::Default;
 use std::fmt::{Display, Formatter};
 
 pub use direction::Direction;
-pub use door::{Door, DoorError};
+pub use door::{Closeable, Door, DoorError, Lockable};
 pub use exit::{Exit, Exits};
+pub use flags::{RoomFlag, SectorType};
 
-#[derive(Copy, Clone, Debug, Deserialize, Hash, Eq, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Hash, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct RoomId(u32);
 
 impl Default for RoomId {
@@ -20,6 +22,12 @@ impl Default for RoomId {
     }
 }
 
+impl From<u32> for RoomId {
+    fn from(id: u32) -> RoomId {
+        RoomId(id)
+    }
+}
+
 impl Display for RoomId {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         self.0.fmt(f)
@@ -29,15 +37,33 @@ impl Display for RoomId {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct RoomDef {
-    id: RoomId,
-    name: String,
-    description: String,
-    exits: Exits,
+    pub(crate) id: RoomId,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    // Shown instead of the description between sunset and sunrise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) night_description: Option<String>,
+    pub(crate) exits: Exits,
     #[serde(default)]
-    load_objects: Vec<ObjectId>,
-    // flags
-    // sector type
-    // extra descs
+    pub(crate) load_objects: Vec<ObjectId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) flags: Vec<RoomFlag>,
+    #[serde(default)]
+    pub(crate) sector: SectorType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) extra_descs: Vec<ExtraDescription>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExtraDescription {
+    pub keywords: Vec<String>,
+    pub description: String,
+}
+
+impl ExtraDescription {
+    pub fn matches(&self, keyword: &str) -> bool {
+        self.keywords.iter().any(|kw| kw.starts_with(keyword))
+    }
 }
 
 #[derive(Debug, Default)]
@@ -46,11 +72,12 @@ pub struct Room {
     pub area: usize,
     pub name: String,
     pub description: String,
+    pub night_description: Option<String>,
     pub exits: Exits,
     pub object_ids: Vec<ObjectId>, // FIXME: this is not real, it's for testing
-    // flags
-    // sector type
-    // extra descs
+    pub flags: Vec<RoomFlag>,
+    pub sector: SectorType,
+    pub extra_descs: Vec<ExtraDescription>,
     // objects
     // characters
 }
@@ -61,10 +88,49 @@ impl Room {
             id: room_def.id,
             name: room_def.name,
             description: room_def.description.trim().to_string(),
+            night_description: room_def
+                .night_description
+                .map(|description| description.trim().to_string()),
             exits: room_def.exits,
             object_ids: room_def.load_objects,
+            flags: room_def.flags,
+            sector: room_def.sector,
+            extra_descs: room_def.extra_descs,
             area,
             ..Default::default()
         }
     }
+
+    pub fn to_prototype(&self) -> RoomDef {
+        RoomDef {
+            id: self.id,
+            name: self.name.clone(),
+            description: format!("{}\n", self.description),
+            night_description: self
+                .night_description
+                .as_ref()
+                .map(|description| format!("{}\n", description)),
+            exits: self.exits.clone(),
+            load_objects: self.object_ids.clone(),
+            flags: self.flags.clone(),
+            sector: self.sector,
+            extra_descs: self.extra_descs.clone(),
+        }
+    }
+
+    pub fn has_flag(&self, flag: RoomFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    /// Outdoor rooms see the sky: the sun, the weather, and the dark at night.
+    pub fn is_outdoors(&self) -> bool {
+        !self.has_flag(RoomFlag::Indoors) && self.sector != SectorType::Inside
+    }
+
+    pub fn extra_description(&self, keyword: &str) -> Option<&str> {
+        self.extra_descs
+            .iter()
+            .find(|extra| extra.matches(keyword))
+            .map(|extra| extra.description.as_str())
+    }
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "DirectionDef", into = "DirectionDef")]
 pub enum Direction {
     North,
     South,
@@ -16,6 +18,23 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub fn parse(name: &str) -> Option<Direction> {
+        if name.is_empty() {
+            return None;
+        }
+        [
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+            Direction::Up,
+            Direction::Down,
+        ]
+        .iter()
+        .find(|dir| dir.matches(name))
+        .cloned()
+    }
+
     pub fn leaving(&self) -> &str {
         match self {
             Direction::North => "north",
@@ -40,8 +59,19 @@ impl Direction {
         }
     }
 
+    pub fn reverse(&self) -> Option<Direction> {
+        match self {
+            Direction::North => Some(Direction::South),
+            Direction::South => Some(Direction::North),
+            Direction::East => Some(Direction::West),
+            Direction::West => Some(Direction::East),
+            Direction::Up => Some(Direction::Down),
+            Direction::Down => Some(Direction::Up),
+            Direction::Custom { .. } => None,
+        }
+    }
+
     pub fn matches(&self, command: &str) -> bool {
-        // TODO: nsewud directions should also be able to have their own keywords
         match self {
             Direction::North => "north".starts_with(command),
             Direction::South => "south".starts_with(command),
@@ -53,3 +83,83 @@ impl Direction {
         }
     }
 }
+
+// The usual directions are written by name, and custom ones as their names and
+// keywords, e.g. `dir = { to_name = "in", from_name = "outside", keywords = ["in"] }`.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum DirectionDef {
+    Named(String),
+    Custom {
+        to_name: String,
+        from_name: String,
+        keywords: Vec<String>,
+    },
+}
+
+impl TryFrom<DirectionDef> for Direction {
+    type Error = String;
+
+    fn try_from(def: DirectionDef) -> Result<Direction, String> {
+        match def {
+            DirectionDef::Named(name) => Direction::parse(&name.to_ascii_lowercase())
+                .filter(|dir| dir.leaving().eq_ignore_ascii_case(&name))
+                .ok_or_else(|| format!("unknown direction {}", name)),
+            DirectionDef::Custom {
+                to_name,
+                from_name,
+                keywords,
+            } => Ok(Direction::Custom {
+                to_name,
+                from_name,
+                keywords,
+            }),
+        }
+    }
+}
+
+impl From<Direction> for DirectionDef {
+    fn from(dir: Direction) -> DirectionDef {
+        match dir {
+            Direction::North => DirectionDef::Named(String::from("North")),
+            Direction::South => DirectionDef::Named(String::from("South")),
+            Direction::East => DirectionDef::Named(String::from("East")),
+            Direction::West => DirectionDef::Named(String::from("West")),
+            Direction::Up => DirectionDef::Named(String::from("Up")),
+            Direction::Down => DirectionDef::Named(String::from("Down")),
+            Direction::Custom {
+                to_name,
+                from_name,
+                keywords,
+            } => DirectionDef::Custom {
+                to_name,
+                from_name,
+                keywords,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Direction;
+    use crate::room::{Exit, RoomId};
+
+    #[test]
+    fn custom_direction_round_trips() {
+        let exit = Exit::new(
+            RoomId::from(2),
+            Direction::Custom {
+                to_name: String::from("into the wardrobe"),
+                from_name: String::from("the wardrobe"),
+                keywords: vec![String::from("wardrobe")],
+            },
+        );
+        let toml = toml::to_string(&exit).unwrap();
+        let parsed: Exit = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.dir, exit.dir);
+        let parsed: Exit = toml::from_str("to = 1\ndir = \"North\"").unwrap();
+        assert_eq!(parsed.dir, Direction::North);
+        assert!(toml::from_str::<Exit>("to = 1\ndir = \"Sideways\"").is_err());
+    }
+}
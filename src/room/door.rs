@@ -15,12 +15,50 @@ pub enum Lockable {
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(from = "DoorDef", into = "DoorDef")]
 pub enum Door {
     None,
     Closable(Closeable),
     Lockable(Closeable, Lockable),
 }
 
+// TOML can't hold enum variants that carry data, so area files write a door as
+// a flat table, e.g. `door = { lockable = true, locked = true }`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+struct DoorDef {
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    lockable: bool,
+    #[serde(default)]
+    locked: bool,
+}
+
+impl From<DoorDef> for Door {
+    fn from(def: DoorDef) -> Door {
+        let closed = if def.closed || def.locked {
+            Closeable::Closed
+        } else {
+            Closeable::Open
+        };
+        match (def.lockable || def.locked, def.locked) {
+            (true, true) => Door::Lockable(closed, Lockable::Locked),
+            (true, false) => Door::Lockable(closed, Lockable::Unlocked),
+            _ => Door::Closable(closed),
+        }
+    }
+}
+
+impl From<Door> for DoorDef {
+    fn from(door: Door) -> DoorDef {
+        DoorDef {
+            closed: door.is_closed(),
+            lockable: matches!(door, Door::Lockable(..)),
+            locked: matches!(door, Door::Lockable(_, Lockable::Locked)),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum DoorError {
     NoDoor,
@@ -57,6 +95,10 @@ impl Door {
         !self.is_closed()
     }
 
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Door::Lockable(_, Lockable::Locked))
+    }
+
     pub fn open(&self) -> Result<Self, DoorError> {
         use Closeable::*;
         use Lockable::*;
@@ -1,13 +1,73 @@
 use super::{Direction, Door, RoomId};
+use crate::object::ObjectId;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct Exit {
     pub to: RoomId,
     pub dir: Direction,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "no_door")]
     pub door: Door,
+    // The object that locks and unlocks the door, if it has a keyhole at all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<ObjectId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub door_name: Option<String>,
+    // Extra words that pick out this exit, on top of its direction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    // What you see when you look this way
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // Hidden exits are left out of the exits list until someone searches for them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    #[serde(skip)]
+    pub revealed: bool,
+}
+
+impl Exit {
+    pub fn new(to: RoomId, dir: Direction) -> Exit {
+        Exit {
+            to,
+            dir,
+            door: Door::None,
+            key: None,
+            door_name: None,
+            keywords: vec![],
+            description: None,
+            hidden: false,
+            revealed: false,
+        }
+    }
+
+    pub fn door_name(&self) -> &str {
+        self.door_name.as_deref().unwrap_or("the door")
+    }
+
+    pub fn is_visible(&self) -> bool {
+        !self.hidden || self.revealed
+    }
+
+    pub fn matches(&self, keyword: &str) -> bool {
+        self.dir.matches(keyword) || self.keywords.iter().any(|kw| kw.starts_with(keyword))
+    }
+
+    fn matches_door(&self, keyword: &str) -> bool {
+        self.door.is_present()
+            && (self.matches(keyword)
+                || "door".starts_with(keyword)
+                || self
+                    .door_name()
+                    .split_whitespace()
+                    .any(|word| word.starts_with(keyword)))
+    }
+}
+
+fn no_door(door: &Door) -> bool {
+    !door.is_present()
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -18,8 +78,58 @@ impl Exits {
         self.0.remove(index);
     }
 
+    /// Carries what's happened to the old exits in play over to these fresh ones:
+    /// doors opened, closed, or locked, and hidden exits that have been found.
+    pub fn keep_state(&mut self, old: &Exits) {
+        for exit in &mut self.0 {
+            if let Some(had) = old.0.iter().find(|had| had.dir == exit.dir) {
+                if std::mem::discriminant(&had.door) == std::mem::discriminant(&exit.door) {
+                    exit.door = had.door;
+                }
+                exit.revealed = exit.hidden && had.revealed;
+            }
+        }
+    }
+
+    /// Finds an exit by direction first, then by the exits' own keywords.
     pub fn get(&self, direction: &str) -> Option<&Exit> {
-        self.0.iter().find(|exit| exit.dir.matches(direction))
+        self.0
+            .iter()
+            .find(|exit| exit.dir.matches(direction))
+            .or_else(|| self.0.iter().find(|exit| exit.matches(direction)))
+    }
+
+    pub fn get_mut(&mut self, direction: &str) -> Option<&mut Exit> {
+        let index = self
+            .0
+            .iter()
+            .position(|exit| exit.dir.matches(direction))
+            .or_else(|| self.0.iter().position(|exit| exit.matches(direction)))?;
+        self.0.get_mut(index)
+    }
+
+    /// Finds a door by its direction or keywords, by a word of its name, or the
+    /// first door at all if asked for "door".
+    pub fn find_door(&self, keyword: &str) -> Option<&Exit> {
+        self.get(keyword)
+            .filter(|exit| exit.door.is_present())
+            .or_else(|| self.0.iter().find(|exit| exit.matches_door(keyword)))
+    }
+
+    /// Finds the exit that leads back to `from` the opposite way of `dir`.
+    pub fn find_return(&mut self, from: RoomId, dir: &Direction) -> Option<&mut Exit> {
+        let reverse = dir.reverse();
+        self.0.iter_mut().find(|exit| {
+            exit.to == from && (reverse.is_none() || reverse.as_ref() == Some(&exit.dir))
+        })
+    }
+
+    /// Adds an exit, replacing any existing exit that leads the same way.
+    pub fn insert(&mut self, exit: Exit) {
+        match self.0.iter_mut().find(|existing| existing.dir == exit.dir) {
+            Some(existing) => *existing = exit,
+            None => self.0.push(exit),
+        }
     }
 }
 
@@ -32,9 +142,8 @@ impl AsRef<Vec<Exit>> for Exits {
 impl Display for Exits {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "[Exits:")?;
-        for exit in &self.0 {
+        for exit in self.0.iter().filter(|exit| exit.is_visible()) {
             write!(f, " {}", exit.dir.leaving())?;
-            // TODO: we can go into greater detail here than DikuMUD does...
             if exit.door.is_closed() {
                 write!(f, "->closed")?;
             }
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RoomFlag {
+    Dark,
+    NoMob,
+    Safe,
+    Indoors,
+    Private,
+    NoRecall,
+    // Books can be shelved here for anyone to read
+    Library,
+}
+
+impl RoomFlag {
+    pub const ALL: &'static [RoomFlag] = &[
+        RoomFlag::Dark,
+        RoomFlag::NoMob,
+        RoomFlag::Safe,
+        RoomFlag::Indoors,
+        RoomFlag::Private,
+        RoomFlag::NoRecall,
+        RoomFlag::Library,
+    ];
+
+    pub fn parse(name: &str) -> Option<RoomFlag> {
+        RoomFlag::ALL
+            .iter()
+            .find(|flag| format!("{:?}", flag).eq_ignore_ascii_case(name))
+            .copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SectorType {
+    #[default]
+    Inside,
+    City,
+    Field,
+    Forest,
+    Hills,
+    Mountain,
+    ShallowWater,
+    DeepWater,
+    Air,
+    Desert,
+}
+
+impl SectorType {
+    pub const ALL: &'static [SectorType] = &[
+        SectorType::Inside,
+        SectorType::City,
+        SectorType::Field,
+        SectorType::Forest,
+        SectorType::Hills,
+        SectorType::Mountain,
+        SectorType::ShallowWater,
+        SectorType::DeepWater,
+        SectorType::Air,
+        SectorType::Desert,
+    ];
+
+    pub fn parse(name: &str) -> Option<SectorType> {
+        SectorType::ALL
+            .iter()
+            .find(|sector| format!("{:?}", sector).eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// How tiring it is to move into a room of this sector. Same numbers as Merc.
+    pub fn movement_cost(&self) -> u32 {
+        match self {
+            SectorType::Inside => 1,
+            SectorType::City => 2,
+            SectorType::Field => 2,
+            SectorType::Forest => 3,
+            SectorType::Hills => 4,
+            SectorType::Mountain => 6,
+            SectorType::ShallowWater => 4,
+            SectorType::DeepWater => 1,
+            SectorType::Air => 10,
+            SectorType::Desert => 6,
+        }
+    }
+
+    pub fn leaving_verb(&self) -> &'static str {
+        match self {
+            SectorType::ShallowWater | SectorType::DeepWater => "swims",
+            SectorType::Air => "flies",
+            SectorType::Mountain | SectorType::Hills => "climbs",
+            _ => "leaves",
+        }
+    }
+
+    pub fn arriving_verb(&self) -> &'static str {
+        match self {
+            SectorType::ShallowWater | SectorType::DeepWater => "swims in",
+            SectorType::Air => "flies in",
+            SectorType::Mountain | SectorType::Hills => "climbs in",
+            _ => "arrives",
+        }
+    }
+}
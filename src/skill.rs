@@ -0,0 +1,337 @@
+use crate::character::{Effect, EffectKind, Position, Stat};
+use crate::object::InstanceId;
+use crate::util;
+use crate::world::{Recipient, World};
+use generational_arena::Index;
+
+// Who a spell can be aimed at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    // Anyone else in the room, and starts a fight
+    Offensive,
+    // Anyone in the room, including the caster
+    Defensive,
+    // Something the caster is carrying
+    Object,
+}
+
+// What a spell does once it's been cast, to the caster and their target.
+#[derive(Clone, Copy)]
+pub enum SpellFn {
+    OnChar(fn(Index, Index, &mut World)),
+    OnObject(fn(Index, InstanceId, &mut World)),
+}
+
+// Whoever or whatever a spell was cast on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpellTarget {
+    Char(Index),
+    Object(InstanceId),
+}
+
+pub struct Skill {
+    pub name: &'static str,
+    pub target: Target,
+    pub mana: u32,
+    // How many pulses the user has to wait afterwards
+    pub lag: u32,
+    pub min_position: Position,
+    // Skills without one are used through their own commands, like rescue.
+    pub spell: Option<SpellFn>,
+}
+
+pub const SKILLS: &[Skill] = &[
+    Skill {
+        name: "bless",
+        target: Target::Defensive,
+        mana: 5,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(bless)),
+    },
+    Skill {
+        name: "continual light",
+        target: Target::Object,
+        mana: 7,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnObject(continual_light)),
+    },
+    Skill {
+        name: "cure light",
+        target: Target::Defensive,
+        mana: 10,
+        lag: 9,
+        min_position: Position::Fighting,
+        spell: Some(SpellFn::OnChar(cure_light)),
+    },
+    Skill {
+        name: "cure poison",
+        target: Target::Defensive,
+        mana: 5,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(cure_poison)),
+    },
+    Skill {
+        name: "detect invisibility",
+        target: Target::Defensive,
+        mana: 5,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(detect_invisibility)),
+    },
+    Skill {
+        name: "giant strength",
+        target: Target::Defensive,
+        mana: 20,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(giant_strength)),
+    },
+    Skill {
+        name: "infravision",
+        target: Target::Defensive,
+        mana: 5,
+        lag: 15,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(infravision)),
+    },
+    Skill {
+        name: "invisibility",
+        target: Target::Defensive,
+        mana: 5,
+        lag: 9,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(invisibility)),
+    },
+    Skill {
+        name: "magic missile",
+        target: Target::Offensive,
+        mana: 15,
+        lag: 9,
+        min_position: Position::Fighting,
+        spell: Some(SpellFn::OnChar(magic_missile)),
+    },
+    Skill {
+        name: "refresh",
+        target: Target::Defensive,
+        mana: 12,
+        lag: 15,
+        min_position: Position::Standing,
+        spell: Some(SpellFn::OnChar(refresh)),
+    },
+    Skill {
+        name: "rescue",
+        target: Target::Defensive,
+        mana: 0,
+        lag: 9,
+        min_position: Position::Fighting,
+        spell: None,
+    },
+];
+
+impl Skill {
+    /// Works the spell's magic on a target of the kind it takes.
+    pub fn cast(&self, caster: Index, target: SpellTarget, world: &mut World) {
+        match (self.spell, target) {
+            (Some(SpellFn::OnChar(spell)), SpellTarget::Char(target)) => {
+                spell(caster, target, world)
+            }
+            (Some(SpellFn::OnObject(spell)), SpellTarget::Object(target)) => {
+                spell(caster, target, world)
+            }
+            _ => log::warn!("{} cast on the wrong kind of target", self.name),
+        }
+    }
+}
+
+// Practice only gets a skill this far. Using it does the rest.
+pub const ADEPT: u32 = 75;
+
+/// Looks up a skill by the start of its name.
+pub fn find(name: &str) -> Option<&'static Skill> {
+    let name = name.to_ascii_lowercase();
+    SKILLS.iter().find(|skill| skill.name.starts_with(&name))
+}
+
+pub fn find_spell(name: &str) -> Option<&'static Skill> {
+    let name = name.to_ascii_lowercase();
+    SKILLS
+        .iter()
+        .find(|skill| skill.spell.is_some() && skill.name.starts_with(&name))
+}
+
+/// How much a practice session teaches, which comes easier to the clever.
+pub fn practice_gain(intelligence: u32) -> u32 {
+    (intelligence / 2).max(1)
+}
+
+/// The percent chance that using a skill teaches something. There's less left
+/// to learn the better it's known, and failing teaches less than succeeding.
+pub fn improve_chance(learned: u32, succeeded: bool) -> u32 {
+    let left = 100u32.saturating_sub(learned);
+    if succeeded {
+        (left / 4).max(1)
+    } else {
+        (left / 8).max(1)
+    }
+}
+
+// Messages the character if there's anyone at the other end to read it.
+fn tell(world: &mut World, char_idx: Index, message: &str) {
+    if world.characters[char_idx].connection().is_some() {
+        world.msg_char(message, Recipient::Subject(char_idx));
+    }
+}
+
+fn cure_light(caster: Index, target: Index, world: &mut World) {
+    let vitals = world.characters[target].vitals_mut();
+    vitals.hp = (vitals.hp + util::random_range(4, 12)).min(vitals.max_hp);
+    tell(world, target, "You feel better!");
+    if caster != target {
+        tell(world, caster, "Ok.");
+    }
+}
+
+fn cure_poison(caster: Index, target: Index, world: &mut World) {
+    if world.characters[target].remove_effect(EffectKind::Poison) {
+        tell(world, target, "A warm feeling runs through your body.");
+        if caster != target {
+            tell(world, caster, "Ok.");
+        }
+    } else {
+        tell(world, caster, "Nothing seems to happen.");
+    }
+}
+
+fn infravision(caster: Index, target: Index, world: &mut World) {
+    let char = &mut world.characters[target];
+    if char.has_effect(EffectKind::Infravision) {
+        tell(world, caster, "Their eyes already glow red.");
+        return;
+    }
+    char.add_effect(Effect::new(EffectKind::Infravision, 12));
+    let room_id = char.in_room();
+    let name = util::capitalize(char.formal_name());
+    tell(world, target, EffectKind::Infravision.start_message());
+    world.msg_char(
+        &format!("{}'s eyes glow red.", name),
+        Recipient::NotSubject(target, room_id),
+    );
+}
+
+// Starts an effect that doesn't need anyone else to notice, unless it's already going.
+fn enchant(caster: Index, target: Index, effect: Effect, already: &str, world: &mut World) {
+    let char = &mut world.characters[target];
+    if char.has_effect(effect.kind) {
+        tell(world, caster, already);
+        return;
+    }
+    char.add_effect(effect);
+    tell(world, target, effect.kind.start_message());
+    if caster != target {
+        tell(world, caster, "Ok.");
+    }
+}
+
+fn bless(caster: Index, target: Index, world: &mut World) {
+    let effect = Effect::new(EffectKind::Bless, 10).with_modifier(Stat::Hit, 5);
+    enchant(caster, target, effect, "They are already blessed.", world);
+}
+
+fn continual_light(caster: Index, target: InstanceId, world: &mut World) {
+    let char = &world.characters[caster];
+    let obj = match char.inventory.iter().find(|obj| obj.instance() == target) {
+        Some(obj) => obj,
+        None => return,
+    };
+    if obj.has_effect(EffectKind::Glow) {
+        tell(world, caster, "It's already glowing.");
+        return;
+    }
+    obj.add_effect(Effect::new(EffectKind::Glow, 24));
+    let message = format!("{} glows with a white light.", util::capitalize(obj.name()));
+    let room_id = char.in_room();
+    world.msg_char(&message, Recipient::All(room_id));
+}
+
+fn detect_invisibility(caster: Index, target: Index, world: &mut World) {
+    let effect = Effect::new(EffectKind::DetectInvisible, 12);
+    enchant(
+        caster,
+        target,
+        effect,
+        "They can already see invisible things.",
+        world,
+    );
+}
+
+fn giant_strength(caster: Index, target: Index, world: &mut World) {
+    let effect = Effect::new(EffectKind::GiantStrength, 8).with_modifier(Stat::Strength, 2);
+    enchant(
+        caster,
+        target,
+        effect,
+        "They are already as strong as they can get.",
+        world,
+    );
+}
+
+fn invisibility(caster: Index, target: Index, world: &mut World) {
+    let char = &mut world.characters[target];
+    if char.has_effect(EffectKind::Invisible) {
+        tell(world, caster, "They are already invisible.");
+        return;
+    }
+    // Everyone else sees them go before they're gone.
+    let room_id = char.in_room();
+    let name = util::capitalize(char.formal_name());
+    world.msg_char(
+        &format!("{} fades out of existence.", name),
+        Recipient::NotSubject(target, room_id),
+    );
+    world.characters[target].add_effect(Effect::new(EffectKind::Invisible, 12));
+    tell(world, target, EffectKind::Invisible.start_message());
+    if caster != target {
+        tell(world, caster, "Ok.");
+    }
+}
+
+fn magic_missile(caster: Index, target: Index, world: &mut World) {
+    let intelligence = world.characters[caster].attributes().intelligence;
+    let damage = util::random_range(3, 8) + intelligence.saturating_sub(13) / 2;
+    world.hit(caster, target, damage, Some("magic missile"));
+}
+
+fn refresh(caster: Index, target: Index, world: &mut World) {
+    let vitals = world.characters[target].vitals_mut();
+    vitals.moves = (vitals.moves + util::random_range(10, 20)).min(vitals.max_moves);
+    tell(world, target, "You feel less tired.");
+    if caster != target {
+        tell(world, caster, "Ok.");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, find_spell, improve_chance, practice_gain};
+
+    #[test]
+    fn finds_by_prefix() {
+        assert_eq!(find("cure").map(|skill| skill.name), Some("cure light"));
+        assert_eq!(find("CURE P").map(|skill| skill.name), Some("cure poison"));
+        assert_eq!(find("res").map(|skill| skill.name), Some("rescue"));
+        assert!(find_spell("rescue").is_none());
+        assert!(find("fireball").is_none());
+    }
+
+    #[test]
+    fn learning() {
+        assert_eq!(practice_gain(13), 6);
+        assert_eq!(practice_gain(1), 1);
+        assert!(improve_chance(10, true) > improve_chance(10, false));
+        assert!(improve_chance(10, true) > improve_chance(90, true));
+        assert_eq!(improve_chance(100, false), 1);
+    }
+}
@@ -0,0 +1,169 @@
+// The game calendar. A game hour passes every tick, and the calendar is
+// figured from the real clock at boot so that it carries on across reboots.
+
+const HOURS_PER_DAY: u64 = 24;
+const DAYS_PER_MONTH: u64 = 35;
+const MONTHS_PER_YEAR: u64 = 17;
+
+const DAY_NAMES: &[&str] = &[
+    "the Moon",
+    "the Bull",
+    "Deception",
+    "Thunder",
+    "Freedom",
+    "the Great Gods",
+    "the Sun",
+];
+
+const MONTH_NAMES: &[&str] = &[
+    "Winter",
+    "the Winter Wolf",
+    "the Frost Giant",
+    "the Old Forces",
+    "the Grand Struggle",
+    "the Spring",
+    "Nature",
+    "Futility",
+    "the Dragon",
+    "the Sun",
+    "the Heat",
+    "the Battle",
+    "the Dark Shades",
+    "the Shadows",
+    "the Long Shadows",
+    "the Ancient Darkness",
+    "the Great Evil",
+];
+
+// Where the sun is in the sky. Outdoor rooms that aren't lit by anything
+// else go dark once the sun sets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Sunlight {
+    Dark,
+    Rise,
+    #[default]
+    Light,
+    Set,
+}
+
+impl Sunlight {
+    pub fn is_daylight(&self) -> bool {
+        matches!(self, Sunlight::Rise | Sunlight::Light)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GameTime {
+    pub hour: u64,
+    pub day: u64,
+    pub month: u64,
+    pub year: u64,
+}
+
+impl GameTime {
+    pub fn from_hours(hours: u64) -> GameTime {
+        let days = hours / HOURS_PER_DAY;
+        let months = days / DAYS_PER_MONTH;
+        GameTime {
+            hour: hours % HOURS_PER_DAY,
+            day: days % DAYS_PER_MONTH,
+            month: months % MONTHS_PER_YEAR,
+            year: months / MONTHS_PER_YEAR,
+        }
+    }
+
+    /// Moves the clock on by an hour. Returns the sunlight if the sun just moved.
+    pub fn advance(&mut self) -> Option<Sunlight> {
+        self.hour += 1;
+        if self.hour == HOURS_PER_DAY {
+            self.hour = 0;
+            self.day += 1;
+        }
+        if self.day == DAYS_PER_MONTH {
+            self.day = 0;
+            self.month += 1;
+        }
+        if self.month == MONTHS_PER_YEAR {
+            self.month = 0;
+            self.year += 1;
+        }
+        match self.hour {
+            5 | 6 | 19 | 20 => Some(self.sunlight()),
+            _ => None,
+        }
+    }
+
+    pub fn sunlight(&self) -> Sunlight {
+        match self.hour {
+            5 => Sunlight::Rise,
+            6..=18 => Sunlight::Light,
+            19 => Sunlight::Set,
+            _ => Sunlight::Dark,
+        }
+    }
+
+    pub fn is_winter(&self) -> bool {
+        self.month >= 9
+    }
+}
+
+impl std::fmt::Display for GameTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hour = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        let day = self.day + 1;
+        let suffix = match (day % 10, day % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        write!(
+            f,
+            "It is {} o'clock {}, Day of {}, {}{} the Month of {}.",
+            hour,
+            if self.hour >= 12 { "pm" } else { "am" },
+            DAY_NAMES[(self.day % DAY_NAMES.len() as u64) as usize],
+            day,
+            suffix,
+            MONTH_NAMES[self.month as usize],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GameTime, Sunlight};
+
+    #[test]
+    fn rolls_over() {
+        let mut time = GameTime::from_hours(24 * 35 * 17 - 1);
+        assert_eq!(time.year, 0);
+        assert_eq!(time.hour, 23);
+        time.advance();
+        assert_eq!(time, GameTime::from_hours(24 * 35 * 17));
+        assert_eq!(time.year, 1);
+        assert_eq!((time.hour, time.day, time.month), (0, 0, 0));
+    }
+
+    #[test]
+    fn sun_moves() {
+        let mut time = GameTime::from_hours(4);
+        assert_eq!(time.sunlight(), Sunlight::Dark);
+        assert_eq!(time.advance(), Some(Sunlight::Rise));
+        assert_eq!(time.advance(), Some(Sunlight::Light));
+        assert_eq!(time.advance(), None);
+    }
+
+    #[test]
+    fn displays() {
+        let time = GameTime::from_hours(24 * 2 + 13);
+        assert_eq!(
+            time.to_string(),
+            "It is 1 o'clock pm, Day of Deception, 3rd the Month of Winter."
+        );
+    }
+}
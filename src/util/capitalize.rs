@@ -0,0 +1,20 @@
+/// Uppercases the first letter, for names that start a sentence ("the door" -> "The door").
+pub fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::capitalize;
+
+    #[test]
+    fn capitalizes() {
+        assert_eq!(capitalize("the door"), "The door");
+        assert_eq!(capitalize("Rhane"), "Rhane");
+        assert_eq!(capitalize(""), "");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::util::HasKeywords;
+use crate::util::{HasKeywords, Selection, Target};
 use intrusive_collections::{linked_list::LinkedListOps, Adapter, LinkedList, PointerOps};
 
 pub fn pluck_item_from_list<A>(
@@ -20,6 +20,27 @@ where
     None
 }
 
+/// Removes everything a target like `all.sword` or `5*arrow` picks out of the list.
+pub fn pluck_items_from_list<A>(
+    list: &mut LinkedList<A>,
+    keyword: &str,
+) -> Vec<<<A as Adapter>::PointerOps as PointerOps>::Pointer>
+where
+    A: Adapter,
+    A::LinkOps: LinkedListOps,
+    <<A as Adapter>::PointerOps as PointerOps>::Value: HasKeywords,
+{
+    let ptrs: Vec<*const <<A as Adapter>::PointerOps as PointerOps>::Value> =
+        Target::parse(keyword)
+            .select(list.iter())
+            .into_iter()
+            .map(|obj| obj as *const _)
+            .collect();
+    ptrs.into_iter()
+        .filter_map(|ptr| unsafe { list.cursor_mut_from_ptr(ptr) }.remove())
+        .collect()
+}
+
 pub fn find_item_by_keyword<'list, A>(
     list: &'list LinkedList<A>,
     keyword: &str,
@@ -29,22 +50,19 @@ where
     A::LinkOps: LinkedListOps,
     <<A as Adapter>::PointerOps as PointerOps>::Value: HasKeywords,
 {
-    let mut found = None;
+    find_by_keyword(list.iter(), keyword)
+}
 
-    'obj_list: for obj in list.iter() {
-        for kw in obj.keywords() {
-            let is_match = kw.starts_with(keyword);
-            let exact = is_match && kw.len() == keyword.len();
-            if exact {
-                found = Some(obj);
-                break 'obj_list;
-            }
-            if is_match && found.is_none() {
-                found = Some(obj);
-                break;
-            }
-        }
+/// Finds the item a keyword picks out, which may be numbered like `2.sword`.
+/// Targets for more than one thing, like `all.sword`, find nothing here.
+pub fn find_by_keyword<'a, T, I>(items: I, keyword: &str) -> Option<&'a T>
+where
+    T: HasKeywords + ?Sized + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let target = Target::parse(keyword);
+    match target.selection {
+        Selection::Nth(_) => target.select(items).into_iter().next(),
+        Selection::All | Selection::Count(_) => None,
     }
-
-    found
 }
@@ -1,58 +1,94 @@
+use crate::character::EffectKind;
 use crate::room::RoomId;
-use crate::util::find_item_by_keyword;
+use crate::util::{self, find_item_by_keyword};
 use crate::world::World;
 use generational_arena::Index;
 use std::io::{Result as IoResult, Write};
 
 pub fn look_room(conn_idx: Index, room_id: RoomId, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let can_see = world.can_see_room(char_idx, room_id);
+    let visible_chars: Vec<Index> = world.room_chars[&room_id]
+        .iter()
+        .copied()
+        .filter(|idx| *idx != char_idx && world.can_see_char(char_idx, *idx))
+        .collect();
     let conn = world.connections.get_mut(conn_idx).unwrap();
+    if !can_see {
+        return write!(conn, "It is pitch black...\r\n");
+    }
     let room;
-    let chars_in_room;
     let objs_in_room;
     {
         room = world.rooms.get(&room_id).expect("Unwrapped None room");
-        chars_in_room = world
-            .room_chars
-            .get(&room_id)
-            .expect("Unwrapped None room chars");
         objs_in_room = world
             .room_objs
             .get(&room_id)
             .expect("Unwrapped None room objs");
     };
 
-    write!(
-        conn,
-        "{}\r\n{}\r\n{}\r\n",
-        &room.name, &room.exits, &room.description
-    )?;
+    write!(conn, "{}\r\n", &room.name)?;
+    if conn.player().preferences().auto_exits {
+        write!(conn, "{}\r\n", &room.exits)?;
+    }
+    let description = match &room.night_description {
+        Some(night) if !world.time.sunlight().is_daylight() => night,
+        _ => &room.description,
+    };
+    write!(conn, "{}\r\n", description)?;
+    match world
+        .shelves
+        .get(&room_id)
+        .map(|shelf| shelf.iter().count())
+    {
+        Some(1) => write!(conn, "A book sits on the shelves here.\r\n")?,
+        Some(count) if count > 1 => write!(conn, "{} books sit on the shelves here.\r\n", count)?,
+        _ => {}
+    }
 
-    for obj in objs_in_room {
-        write!(conn, "    {}\r\n", obj.room_description())?;
+    let obj_lines: Vec<String> = objs_in_room
+        .iter()
+        .map(|obj| {
+            let glowing = if obj.has_effect(EffectKind::Glow) {
+                "(Glowing) "
+            } else {
+                ""
+            };
+            format!("{}{}", glowing, obj.room_description())
+        })
+        .collect();
+    for (count, line) in util::stack_lines(obj_lines.iter().map(String::as_str)) {
+        write!(conn, "{}\r\n", util::stacked_line(count, line))?;
     }
 
-    let self_idx = conn.character;
-    for char_idx in chars_in_room {
-        if *char_idx == self_idx {
-            continue;
-        }
-        if let Some(ch) = world.characters.get(*char_idx) {
-            write!(conn, "{}\r\n", ch.room_description())?;
+    for char_idx in visible_chars {
+        if let Some(ch) = world.characters.get(char_idx) {
+            let invisible = if ch.has_effect(EffectKind::Invisible) {
+                "(Invis) "
+            } else {
+                ""
+            };
+            write!(conn, "{}{}\r\n", invisible, ch.room_description())?;
         }
     }
     Ok(())
 }
 
 pub fn look_at(conn_idx: Index, room_id: RoomId, target: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let can_see = world.can_see_room(char_idx, room_id);
+    let visible_chars: Vec<Index> = world.room_chars[&room_id]
+        .iter()
+        .copied()
+        .filter(|idx| world.can_see_char(char_idx, *idx))
+        .collect();
     let conn = world.connections.get_mut(conn_idx).unwrap();
-    let chars_in_room;
+    if !can_see {
+        return write!(conn, "It is pitch black...\r\n");
+    }
     let inventory;
     let objs_in_room;
     {
-        chars_in_room = world
-            .room_chars
-            .get(&room_id)
-            .expect("Unwrapped None room chars");
         inventory = &world.characters.get(conn.character).expect("Unwrapped None character").inventory;
         objs_in_room = world
             .room_objs
@@ -60,20 +96,36 @@ pub fn look_at(conn_idx: Index, room_id: RoomId, target: &str, world: &mut World
             .expect("Unwrapped None room objs");
     };
 
-    for char_idx in chars_in_room {
-        if let Some(target) = world
-            .characters
-            .get(*char_idx)
-            .filter(|ch| ch.keywords().iter().any(|kw| kw.starts_with(target)))
-        {
+    let characters = &world.characters;
+    let target_char = util::find_by_keyword(
+        visible_chars.iter().filter_map(|idx| characters.get(*idx)),
+        target,
+    );
+    if let Some(target) = target_char {
+        write!(
+            conn,
+            "You look at {}.\r\n{}\r\n",
+            target.formal_name(),
+            target.description()
+        )?;
+        if !target.equipment.is_empty() {
             write!(
                 conn,
-                "You look at {}.\r\n{}\r\n",
+                "\r\n{} is using:\r\n{}",
                 target.formal_name(),
-                target.description()
+                target.equipment
             )?;
-            return Ok(());
         }
+        return Ok(());
+    }
+    let equipped = world.characters.get(conn.character).and_then(|char| {
+        char.equipment
+            .find(target)
+            .and_then(|slot| char.equipment.get(slot))
+    });
+    if let Some(obj) = equipped {
+        write!(conn, "{}\r\n", obj.description())?;
+        return Ok(());
     }
     if let Some(obj) = find_item_by_keyword(inventory, target) {
         write!(conn, "{}\r\n", obj.description())?;
@@ -82,8 +134,73 @@ pub fn look_at(conn_idx: Index, room_id: RoomId, target: &str, world: &mut World
     if let Some(obj) = find_item_by_keyword(objs_in_room, target)
     {
         write!(conn, "{}\r\n", obj.description())?;
+    } else if let Some(description) = world
+        .rooms
+        .get(&room_id)
+        .and_then(|room| room.extra_description(target))
+    {
+        write!(conn, "{}\r\n", description.trim_end())?;
+    } else if let Some(exit) = world
+        .rooms
+        .get(&room_id)
+        .and_then(|room| room.exits.get(target))
+        .filter(|exit| exit.is_visible())
+    {
+        match &exit.description {
+            Some(description) => write!(conn, "{}\r\n", description.trim_end())?,
+            None => write!(conn, "You see nothing special that way.\r\n")?,
+        }
+        if exit.door.is_present() {
+            let state = if exit.door.is_closed() {
+                "closed"
+            } else {
+                "open"
+            };
+            write!(
+                conn,
+                "{} is {}.\r\n",
+                util::capitalize(exit.door_name()),
+                state
+            )?;
+        }
     } else {
         write!(conn, "You don't see any {} here.\r\n", target)?;
     }
     Ok(())
 }
+
+pub fn look_in(conn_idx: Index, room_id: RoomId, target: &str, world: &mut World) -> IoResult<()> {
+    let char_idx = world.connections.get(conn_idx).unwrap().character;
+    let can_see = world.can_see_room(char_idx, room_id);
+    let conn = world.connections.get_mut(conn_idx).unwrap();
+    if !can_see {
+        return write!(conn, "It is pitch black...\r\n");
+    }
+    let inventory = &world
+        .characters
+        .get(char_idx)
+        .expect("Unwrapped None character")
+        .inventory;
+    let room_objs = &world.room_objs[&room_id];
+    let obj = match find_item_by_keyword(inventory, target)
+        .or_else(|| find_item_by_keyword(room_objs, target))
+    {
+        Some(obj) => obj,
+        None => return write!(conn, "You don't see any {} here.\r\n", target),
+    };
+    match obj.container() {
+        None => write!(conn, "That's not a container.\r\n"),
+        Some(container) if container.is_closed() => write!(conn, "It is closed.\r\n"),
+        Some(_) => {
+            let contents = obj.contents.borrow();
+            if contents.is_empty() {
+                return write!(conn, "{} is empty.\r\n", util::capitalize(obj.name()));
+            }
+            write!(conn, "{} contains:\r\n", util::capitalize(obj.name()))?;
+            for (count, line) in util::stack_lines(contents.iter().map(|inner| inner.name())) {
+                write!(conn, "{}\r\n", util::stacked_line(count, line))?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,13 +1,23 @@
+mod capitalize;
 mod find_partial;
 mod has_keywords;
 mod list;
 mod look;
+mod random;
 mod save;
+mod stack;
 mod take_argument;
+mod target;
 
+pub use capitalize::capitalize;
 pub use find_partial::find_partial;
 pub use has_keywords::HasKeywords;
-pub use list::{find_item_by_keyword, pluck_item_from_list};
-pub use look::{look_at, look_room};
+pub use list::{
+    find_by_keyword, find_item_by_keyword, pluck_item_from_list, pluck_items_from_list,
+};
+pub use look::{look_at, look_in, look_room};
+pub use random::{random_percent, random_range, random_u64};
 pub use save::save;
+pub use stack::{stack_lines, stacked_line};
 pub use take_argument::{take_argument, take_command};
+pub use target::{Selection, Target};
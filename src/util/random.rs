@@ -0,0 +1,38 @@
+// Dice rolls for anything that needs a little luck. There's no need for a
+// seeded generator, so this just asks the OS for some bytes each time.
+
+/// A number from `low` to `high`, inclusive.
+pub fn random_range(low: u32, high: u32) -> u32 {
+    if high <= low {
+        return low;
+    }
+    let mut bytes = [0u8; 4];
+    getrandom::getrandom(&mut bytes).expect("Couldn't get random bytes");
+    low + u32::from_le_bytes(bytes) % (high - low + 1)
+}
+
+/// A number from 1 to 100, for rolling against a percent chance.
+pub fn random_percent() -> u32 {
+    random_range(1, 100)
+}
+
+/// A number from anywhere in the u64 range, for ids that shouldn't collide.
+pub fn random_u64() -> u64 {
+    let mut bytes = [0u8; 8];
+    getrandom::getrandom(&mut bytes).expect("Couldn't get random bytes");
+    u64::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::random_range;
+
+    #[test]
+    fn stays_in_range() {
+        for _ in 0..1000 {
+            let n = random_range(3, 6);
+            assert!((3..=6).contains(&n));
+        }
+        assert_eq!(random_range(5, 5), 5);
+    }
+}
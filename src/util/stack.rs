@@ -0,0 +1,41 @@
+// Lists of objects show identical things once, with a count in front.
+
+/// Groups identical lines together, in the order each first shows up.
+pub fn stack_lines<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Vec<(usize, &'a str)> {
+    let mut stacks: Vec<(usize, &str)> = vec![];
+    for line in lines {
+        match stacks.iter_mut().find(|(_, seen)| *seen == line) {
+            Some((count, _)) => *count += 1,
+            None => stacks.push((1, line)),
+        }
+    }
+    stacks
+}
+
+/// A line with its count, like `( 3) an old greatsword`. Single things are
+/// padded to line up with the stacked ones.
+pub fn stacked_line(count: usize, line: &str) -> String {
+    if count > 1 {
+        format!("({:2}) {}", count, line)
+    } else {
+        format!("     {}", line)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{stack_lines, stacked_line};
+
+    #[test]
+    fn groups_in_first_seen_order() {
+        let lines = vec!["a sword", "a torch", "a sword", "a sword"];
+        assert_eq!(stack_lines(lines), vec![(3, "a sword"), (1, "a torch")]);
+    }
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(stacked_line(3, "a sword"), "( 3) a sword");
+        assert_eq!(stacked_line(12, "a sword"), "(12) a sword");
+        assert_eq!(stacked_line(1, "a sword"), "     a sword");
+    }
+}
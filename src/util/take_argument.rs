@@ -85,6 +85,7 @@ pub fn take_argument(input: &str) -> (Option<&str>, &str) {
 #[rustfmt::skip]
 mod test {
     use super::{take_command, take_argument};
+    use crate::util::{HasKeywords, Selection, Target};
 
     #[test]
     fn command_no_arguments() {
@@ -144,4 +145,66 @@ mod test {
         let (kw4, _rest) = take_argument(rest);
         assert_eq!(&[kw1, kw2, kw3, kw4], &[Some("put"), Some("important thing"), Some("in"), Some("big box")]);
     }
+
+    struct Thing(Vec<String>);
+
+    impl HasKeywords for Thing {
+        fn keywords(&self) -> &[String] {
+            &self.0
+        }
+    }
+
+    fn things() -> Vec<Thing> {
+        vec![
+            Thing(vec![String::from("swordfish")]),
+            Thing(vec![String::from("sword"), String::from("long")]),
+            Thing(vec![String::from("arrow")]),
+            Thing(vec![String::from("sword"), String::from("short")]),
+            Thing(vec![String::from("arrow")]),
+        ]
+    }
+
+    fn pick<'a>(things: &'a [Thing], argument: &str) -> Vec<&'a str> {
+        Target::parse(argument)
+            .select(things)
+            .into_iter()
+            .map(|thing| thing.0.last().unwrap().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_syntax() {
+        let parse = |arg| Target::parse(arg).selection;
+        assert_eq!(parse("sword"), Selection::Nth(1));
+        assert_eq!(parse("3.sword"), Selection::Nth(3));
+        assert_eq!(parse("all"), Selection::All);
+        assert_eq!(parse("ALL.sword"), Selection::All);
+        assert_eq!(parse("5*arrow"), Selection::Count(5));
+        assert_eq!(Target::parse("all").keyword, None);
+        assert_eq!(Target::parse("all.sword").keyword, Some("sword"));
+        assert_eq!(Target::parse("0.sword").keyword, Some("0.sword"));
+    }
+
+    #[test]
+    fn plain_keyword_prefers_exact() {
+        assert_eq!(pick(&things(), "sword"), vec!["long"]);
+        assert_eq!(pick(&things(), "swo"), vec!["swordfish"]);
+    }
+
+    #[test]
+    fn numbered_counts_matches() {
+        assert_eq!(pick(&things(), "1.sword"), pick(&things(), "sword"));
+        assert_eq!(pick(&things(), "1.sword"), vec!["long"]);
+        assert_eq!(pick(&things(), "2.sword"), vec!["short"]);
+        assert_eq!(pick(&things(), "3.sword"), vec!["swordfish"]);
+        assert!(pick(&things(), "4.sword").is_empty());
+    }
+
+    #[test]
+    fn all_and_counts() {
+        assert_eq!(pick(&things(), "all").len(), 5);
+        assert_eq!(pick(&things(), "all.arrow"), vec!["arrow", "arrow"]);
+        assert_eq!(pick(&things(), "1*arrow"), vec!["arrow"]);
+        assert_eq!(pick(&things(), "5*arrow").len(), 2);
+    }
 }
@@ -0,0 +1,92 @@
+use crate::util::HasKeywords;
+
+// Diku-style target syntax: `sword`, `2.sword`, `all`, `all.sword`, and `5*sword`.
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Selection {
+    // The nth thing that matches, counting from 1
+    Nth(usize),
+    All,
+    // Up to this many of the things that match
+    Count(usize),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Target<'a> {
+    pub selection: Selection,
+    // None matches everything
+    pub keyword: Option<&'a str>,
+}
+
+impl<'a> Target<'a> {
+    pub fn parse(argument: &'a str) -> Target<'a> {
+        let keyword = |kw: &'a str| Some(kw).filter(|kw| !kw.is_empty());
+        if argument.eq_ignore_ascii_case("all") {
+            return Target {
+                selection: Selection::All,
+                keyword: None,
+            };
+        }
+        if let Some((prefix, kw)) = argument.split_once('.') {
+            if prefix.eq_ignore_ascii_case("all") {
+                return Target {
+                    selection: Selection::All,
+                    keyword: keyword(kw),
+                };
+            }
+            if let Some(n) = prefix.parse().ok().filter(|n| *n > 0) {
+                return Target {
+                    selection: Selection::Nth(n),
+                    keyword: keyword(kw),
+                };
+            }
+        }
+        if let Some((count, kw)) = argument.split_once('*') {
+            if let Some(n) = count.parse().ok().filter(|n| *n > 0) {
+                return Target {
+                    selection: Selection::Count(n),
+                    keyword: keyword(kw),
+                };
+            }
+        }
+        Target {
+            selection: Selection::Nth(1),
+            keyword: Some(argument),
+        }
+    }
+
+    pub fn matches<T: HasKeywords + ?Sized>(&self, item: &T) -> bool {
+        match self.keyword {
+            Some(keyword) => item.keywords().iter().any(|kw| kw.starts_with(keyword)),
+            None => true,
+        }
+    }
+
+    /// Picks out the targeted items, in order. Numbering counts items with an
+    /// exact keyword before partial matches, so `sword` and `1.sword` are the
+    /// same thing and `2.sword` is the next one along.
+    pub fn select<'i, T, I>(&self, items: I) -> Vec<&'i T>
+    where
+        T: HasKeywords + ?Sized + 'i,
+        I: IntoIterator<Item = &'i T>,
+    {
+        let matching = items.into_iter().filter(|item| self.matches(*item));
+        match self.selection {
+            Selection::Nth(n) => {
+                let (exact, partial): (Vec<&T>, Vec<&T>) = matching.partition(|item| {
+                    item.keywords()
+                        .iter()
+                        .any(|kw| Some(kw.as_str()) == self.keyword)
+                });
+                exact
+                    .into_iter()
+                    .chain(partial)
+                    .nth(n - 1)
+                    .into_iter()
+                    .collect()
+            }
+            Selection::All => matching.collect(),
+            Selection::Count(n) => matching.take(n).collect(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::util::random_range;
+
+// Each area has its own weather. Barometric pressure drifts around a
+// seasonal norm, and the sky follows it, like in Merc.
+
+const MIN_PRESSURE: i32 = 960;
+const MAX_PRESSURE: i32 = 1040;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sky {
+    Clear,
+    Cloudy,
+    Rain,
+    Storm,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weather {
+    pub sky: Sky,
+    pressure: i32,
+    change: i32,
+}
+
+impl Default for Weather {
+    fn default() -> Weather {
+        Weather {
+            sky: Sky::Clear,
+            pressure: 1010,
+            change: 0,
+        }
+    }
+}
+
+impl Weather {
+    /// Starts off somewhere believable for the time of year.
+    pub fn new(winter: bool) -> Weather {
+        let pressure = if winter {
+            random_range(960, 1020) as i32
+        } else {
+            random_range(980, 1040) as i32
+        };
+        let sky = match pressure {
+            p if p <= 980 => Sky::Storm,
+            p if p <= 1000 => Sky::Rain,
+            p if p <= 1020 => Sky::Cloudy,
+            _ => Sky::Clear,
+        };
+        Weather {
+            sky,
+            pressure,
+            change: 0,
+        }
+    }
+
+    /// Runs once per game hour. Returns what people outside notice, if the sky changed.
+    pub fn update(&mut self, winter: bool) -> Option<&'static str> {
+        let norm = if winter { 985 } else { 1015 };
+        let diff = if self.pressure > norm { -2 } else { 2 };
+        self.change += diff * random_range(1, 4) as i32 + random_range(2, 12) as i32
+            - random_range(2, 12) as i32;
+        self.change = self.change.clamp(-12, 12);
+        self.pressure = (self.pressure + self.change).clamp(MIN_PRESSURE, MAX_PRESSURE);
+
+        let chance = random_range(0, 3) == 0;
+        let (sky, message) = match self.sky {
+            Sky::Clear if self.pressure < 990 || (self.pressure < 1010 && chance) => {
+                (Sky::Cloudy, "The sky is getting cloudy.")
+            }
+            Sky::Cloudy if self.pressure < 970 || (self.pressure < 990 && chance) => {
+                (Sky::Rain, "It starts to rain.")
+            }
+            Sky::Cloudy if self.pressure > 1030 && chance => (Sky::Clear, "The clouds disappear."),
+            Sky::Rain if self.pressure < 970 && chance => {
+                (Sky::Storm, "Lightning flashes in the sky.")
+            }
+            Sky::Rain if self.pressure > 1030 || (self.pressure > 1010 && chance) => {
+                (Sky::Cloudy, "The rain stopped.")
+            }
+            Sky::Storm if self.pressure > 1010 || (self.pressure > 990 && chance) => {
+                (Sky::Rain, "The lightning has stopped.")
+            }
+            _ => return None,
+        };
+        self.sky = sky;
+        Some(message)
+    }
+
+    pub fn describe(&self) -> String {
+        let sky = match self.sky {
+            Sky::Clear => "cloudless",
+            Sky::Cloudy => "cloudy",
+            Sky::Rain => "rainy",
+            Sky::Storm => "lit by flashes of lightning",
+        };
+        let wind = if self.change >= 0 {
+            "a warm southerly breeze blows"
+        } else {
+            "a cold northern gust blows"
+        };
+        format!("The sky is {} and {}.", sky, wind)
+    }
+}
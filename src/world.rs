@@ -1,15 +1,22 @@
-use crate::area::Area;
-use crate::character::{CharId, Character, CharacterData};
-use crate::commands::{lookup_command, CommandFn};
+use crate::area::{Area, AreaLoadError, AreaSaveError};
+use crate::character::{self, CharId, Character, CharacterData, EffectKind, Position, EXPLORE_EXP};
+use crate::combat;
+use crate::commands::{lookup_command, CommandFn, EDITOR_INPUT, WRITING_INPUT};
 use crate::connection::Connection;
-use crate::object::{AllObjectsAdapter, Object, ObjectDef, ObjectId, ObjectInRoomAdapter};
-use crate::room::{Room, RoomId};
-use crate::util::take_command;
+use crate::object::{
+    AllObjectsAdapter, InstanceId, Object, ObjectDef, ObjectId, ObjectInRoomAdapter, Portal,
+};
+use crate::olc::EditTarget;
+use crate::room::{Room, RoomFlag, RoomId, SectorType};
+use crate::time::{GameTime, Sunlight};
+use crate::util::{self, take_command};
+use crate::weather::Weather;
 use ahash::RandomState;
 use generational_arena::{Arena, Index};
 use intrusive_collections::LinkedList;
 use std::collections::HashMap;
 use std::default::Default;
+use std::fmt::{Display, Formatter};
 use std::io::{ErrorKind, Write};
 use std::rc::Rc;
 
@@ -17,6 +24,7 @@ pub struct PendingCommand {
     conn_idx: Index,
     at_room: RoomId,
     command: Option<&'static CommandFn>,
+    min_position: Position,
     arguments: String,
 }
 
@@ -32,6 +40,9 @@ pub struct World {
     pub rooms: HashMap<RoomId, Room, RandomState>,
     pub room_chars: HashMap<RoomId, Vec<Index>, RandomState>, // Linked list?
     pub room_objs: HashMap<RoomId, LinkedList<ObjectInRoomAdapter>, RandomState>,
+    // Books shelved in library rooms, kept apart from whatever's lying on the floor
+    pub shelves: HashMap<RoomId, LinkedList<ObjectInRoomAdapter>, RandomState>,
+    pub time: GameTime,
     pending_commands: std::collections::LinkedList<PendingCommand>,
 }
 
@@ -60,40 +71,316 @@ impl World {
     }
 
     pub fn populate(&mut self) {
-        for npc_def in self.npc_defs.values() {
-            let npc = Character::from_data(npc_def.clone());
-            let in_room = self
-                .room_chars
-                .get_mut(&npc.in_room())
-                .expect("Unwrapped None room chars");
-            let idx = self.characters.insert(npc);
-            self.characters.get_mut(idx).map(|char| char.set_index(idx));
-            in_room.push(idx);
+        let ids: Vec<CharId> = self.npc_defs.keys().copied().collect();
+        for id in ids {
+            self.spawn_npc(id);
         }
 
         // FIXME: hackity hack-hack
         for room in self.rooms.values() {
             for id in &room.object_ids {
-                let obj = Rc::new(Object::from_prototype(&self.object_defs[id]));
+                let def = match self.object_defs.get(id) {
+                    Some(def) => def,
+                    None => {
+                        log::warn!(
+                            "Not loading nonexistant object {} into room {}",
+                            id,
+                            room.id
+                        );
+                        continue;
+                    }
+                };
+                let obj = Rc::new(Object::from_prototype(def));
                 self.objects.push_front(Rc::clone(&obj));
-                let in_room = self
-                    .room_objs
-                    .get_mut(&room.id)
-                    .expect("Unwrapped None room objs");
+                // A library starts out with its books on the shelves.
+                let in_room = if room.has_flag(RoomFlag::Library) && obj.book().is_some() {
+                    self.shelves.entry(room.id).or_default()
+                } else {
+                    self.room_objs
+                        .get_mut(&room.id)
+                        .expect("Unwrapped None room objs")
+                };
                 in_room.push_front(obj);
             }
         }
     }
 
+    /// Puts a fresh copy of an npc in its room, shop stocked and all.
+    fn spawn_npc(&mut self, id: CharId) {
+        let npc = Character::from_data(self.npc_defs[&id].clone());
+        let no_mob = self
+            .rooms
+            .get(&npc.in_room())
+            .is_some_and(|room| room.has_flag(RoomFlag::NoMob));
+        if no_mob {
+            log::warn!("Not spawning npc {} into no-mob room {}", id, npc.in_room());
+            return;
+        }
+        let in_room = match self.room_chars.get_mut(&npc.in_room()) {
+            Some(in_room) => in_room,
+            None => {
+                log::warn!(
+                    "Not spawning npc {} into nonexistant room {}",
+                    id,
+                    npc.in_room()
+                );
+                return;
+            }
+        };
+        let idx = self.characters.insert(npc);
+        self.characters.get_mut(idx).map(|char| char.set_index(idx));
+        in_room.push(idx);
+        self.restock(idx);
+    }
+
+    /// Re-reads an area file and folds its rooms and prototypes into the running world.
+    /// Objects and NPCs that have already been spawned are left alone.
+    pub fn reload_area(&mut self, file_name: &str) -> Result<AreaReload, AreaLoadError> {
+        // Only names of real area files, so nothing outside areas/ gets read.
+        let known = self.areas.iter().any(|area| area.file_name == file_name)
+            || Area::list()?.iter().any(|name| name == file_name);
+        if !known {
+            return Err(AreaLoadError::Unknown(file_name.to_string()));
+        }
+        let mut area_def = Area::load(file_name)?;
+        let mut summary = AreaReload::default();
+
+        let area_npcs = area_def.extract_npcs();
+        let area_objects = area_def.extract_objects();
+        let mut room_defs = area_def.extract_rooms();
+
+        // Nothing may point at a room or object that won't be there once the
+        // area is swapped in, or the next reset falls over it.
+        let existing_idx = self.areas.iter().position(|a| a.file_name == file_name);
+        let room_known = |id: &RoomId| {
+            room_defs.iter().any(|room_def| room_def.id == *id)
+                || self
+                    .rooms
+                    .get(id)
+                    .is_some_and(|room| Some(room.area) != existing_idx)
+        };
+        let (area_npcs, misplaced): (Vec<_>, Vec<_>) = area_npcs
+            .into_iter()
+            .partition(|ch| room_known(&ch.in_room));
+        for ch in misplaced {
+            log::warn!(
+                "Reloading area {}: npc {} is placed in nonexistant room {}; skipping it",
+                file_name,
+                ch.id(),
+                ch.in_room
+            );
+            summary.skipped_npcs.push(ch.id());
+        }
+        let old_area_objects = existing_idx.map_or(&[][..], |idx| &self.areas[idx].objects[..]);
+        let object_known = |id: &ObjectId| {
+            area_objects.iter().any(|obj_def| obj_def.id == *id)
+                || (self.object_defs.contains_key(id) && !old_area_objects.contains(id))
+        };
+        for room_def in &mut room_defs {
+            let room_id = room_def.id;
+            room_def.load_objects.retain(|id| {
+                let known = object_known(id);
+                if !known {
+                    log::warn!(
+                        "Reloading area {}: room {} loads nonexistant object {}; skipping it",
+                        file_name,
+                        room_id,
+                        id
+                    );
+                    summary.skipped_objects.push(*id);
+                }
+                known
+            });
+        }
+
+        let area_idx = match existing_idx {
+            Some(idx) => idx,
+            None => {
+                self.areas.push(Area::from_prototype(area_def));
+                self.areas.len() - 1
+            }
+        };
+
+        let old_npcs = std::mem::take(&mut self.areas[area_idx].npcs);
+        for ch in area_npcs {
+            self.areas[area_idx].npcs.push(ch.id());
+            self.npc_defs.insert(ch.id(), ch);
+            summary.npcs += 1;
+        }
+        for id in old_npcs {
+            if !self.areas[area_idx].npcs.contains(&id) {
+                self.npc_defs.remove(&id);
+            }
+        }
+
+        let old_objects = std::mem::take(&mut self.areas[area_idx].objects);
+        for obj_def in area_objects {
+            self.areas[area_idx].objects.push(obj_def.id);
+            self.object_defs.insert(obj_def.id, obj_def);
+            summary.objects += 1;
+        }
+        for id in old_objects {
+            if !self.areas[area_idx].objects.contains(&id) {
+                self.object_defs.remove(&id);
+            }
+        }
+
+        let old_rooms = std::mem::take(&mut self.areas[area_idx].rooms);
+        for room_def in room_defs {
+            let room = Room::from_prototype(room_def, area_idx);
+            let room_id = room.id;
+            match self.rooms.get_mut(&room_id) {
+                Some(existing) if existing.area != area_idx => {
+                    log::warn!(
+                        "Reloading area {}: room {} belongs to another area; skipping it",
+                        file_name,
+                        room_id
+                    );
+                    continue;
+                }
+                Some(existing) => {
+                    let mut room = room;
+                    room.exits.keep_state(&existing.exits);
+                    *existing = room;
+                    summary.rooms_updated += 1;
+                }
+                None => {
+                    self.rooms.insert(room_id, room);
+                    self.room_chars.insert(room_id, vec![]);
+                    self.room_objs.insert(room_id, Default::default());
+                    summary.rooms_added += 1;
+                }
+            }
+            self.areas[area_idx].rooms.push(room_id);
+        }
+
+        let removed_rooms: Vec<RoomId> = old_rooms
+            .into_iter()
+            .filter(|id| !self.areas[area_idx].rooms.contains(id))
+            .collect();
+        if !removed_rooms.is_empty() {
+            let safe_room = if removed_rooms.contains(&RoomId::default()) {
+                self.rooms
+                    .keys()
+                    .find(|id| !removed_rooms.contains(id))
+                    .copied()
+            } else {
+                Some(RoomId::default())
+            };
+            match safe_room {
+                Some(safe_room) => {
+                    for room_id in removed_rooms {
+                        self.remove_room(room_id, safe_room, &mut summary);
+                    }
+                }
+                None => {
+                    log::error!(
+                        "Reloading area {}: no room left to move characters into; keeping removed rooms",
+                        file_name
+                    );
+                    self.areas[area_idx].rooms.extend(removed_rooms);
+                }
+            }
+        }
+
+        audit_room_exits(&mut self.rooms);
+        self.close_stale_editors();
+        log::info!("Reloaded area {}: {}", file_name, summary);
+        Ok(summary)
+    }
+
+    // Builders editing something a reload just took away have nothing left to edit.
+    fn close_stale_editors(&mut self) {
+        for (_, conn) in &mut self.connections {
+            let exists = match conn.editor.as_ref().map(|editor| editor.target) {
+                Some(EditTarget::Room(id)) => self.rooms.contains_key(&id),
+                Some(EditTarget::Object(id)) => self.object_defs.contains_key(&id),
+                Some(EditTarget::Npc(id)) => self.npc_defs.contains_key(&id),
+                None => true,
+            };
+            if !exists {
+                conn.editor = None;
+                let _ = write!(conn, "What you were editing is gone. You stop editing.\r\n");
+            }
+        }
+    }
+
+    fn remove_room(&mut self, room_id: RoomId, safe_room: RoomId, summary: &mut AreaReload) {
+        let chars = self.room_chars.remove(&room_id).unwrap_or_default();
+        let mut objs = self.room_objs.remove(&room_id).unwrap_or_default();
+        let mut shelved = self.shelves.remove(&room_id).unwrap_or_default();
+        self.rooms.remove(&room_id);
+        summary.rooms_removed += 1;
+
+        for char_idx in chars {
+            self.char_to_room(char_idx, safe_room);
+            self.msg_char(
+                "The world dissolves around you, and you find yourself somewhere else.",
+                Recipient::Subject(char_idx),
+            );
+            summary.chars_relocated += 1;
+        }
+        let safe_objs = self
+            .room_objs
+            .get_mut(&safe_room)
+            .expect("Unwrapped None room objs");
+        while let Some(obj) = objs.pop_front().or_else(|| shelved.pop_front()) {
+            safe_objs.push_front(obj);
+        }
+    }
+
+    pub fn save_area(&self, area_idx: usize) -> Result<(), AreaSaveError> {
+        let area = &self.areas[area_idx];
+        let npcs = area
+            .npcs
+            .iter()
+            .filter_map(|id| self.npc_defs.get(id))
+            .cloned()
+            .collect();
+        let objects = area
+            .objects
+            .iter()
+            .filter_map(|id| self.object_defs.get(id))
+            .cloned()
+            .collect();
+        let rooms = area
+            .rooms
+            .iter()
+            .filter_map(|id| self.rooms.get(id))
+            .map(|room| room.to_prototype())
+            .collect();
+        Area::save(&area.to_prototype(npcs, objects, rooms))
+    }
+
     pub fn read_input(&mut self) {
         for (idx, conn) in &mut self.connections {
             match conn.read() {
                 Ok(input) if !input.is_empty() => {
-                    if let Some((command, rest)) = take_command(&input) {
+                    if conn.writing.is_some() {
+                        let pending = PendingCommand {
+                            conn_idx: idx,
+                            at_room: self.characters[conn.character].in_room(),
+                            command: Some(&WRITING_INPUT),
+                            min_position: Position::Dead,
+                            arguments: input,
+                        };
+                        self.pending_commands.push_back(pending);
+                    } else if conn.editor.is_some() {
+                        let pending = PendingCommand {
+                            conn_idx: idx,
+                            at_room: self.characters[conn.character].in_room(),
+                            command: Some(&EDITOR_INPUT),
+                            min_position: Position::Dead,
+                            arguments: input,
+                        };
+                        self.pending_commands.push_back(pending);
+                    } else if let Some((command, rest)) = take_command(&input) {
+                        let found = lookup_command(command);
                         let pending = PendingCommand {
                             conn_idx: idx,
                             at_room: self.characters[conn.character].in_room(),
-                            command: lookup_command(command),
+                            command: found.map(|(command, _)| command),
+                            min_position: found.map_or(Position::Dead, |(_, position)| position),
                             arguments: rest.to_string(),
                         };
                         self.pending_commands.push_back(pending);
@@ -131,9 +418,43 @@ impl World {
     pub fn run_player_commands(&mut self) {
         let pending_commands = self.pending_commands.split_off(0);
         for pending in pending_commands {
+            // Anyone still recovering from using a skill has to wait their turn.
+            let lagged = self
+                .connections
+                .get(pending.conn_idx)
+                .and_then(|conn| self.characters.get(conn.character))
+                .filter(|char| char.lag() > 0);
+            if let Some(char) = lagged {
+                let at_room = char.in_room();
+                self.pending_commands
+                    .push_back(PendingCommand { at_room, ..pending });
+                continue;
+            }
+            // The room might have been removed by an area reload since the input was read.
+            let at_room = if self.rooms.contains_key(&pending.at_room) {
+                pending.at_room
+            } else {
+                match self
+                    .connections
+                    .get(pending.conn_idx)
+                    .and_then(|conn| self.characters.get(conn.character))
+                {
+                    Some(char) => char.in_room(),
+                    None => continue,
+                }
+            };
+            let position = self
+                .connections
+                .get(pending.conn_idx)
+                .and_then(|conn| self.characters.get(conn.character))
+                .map_or(Position::Standing, |char| char.position());
             match pending.command {
+                Some(_) if position < pending.min_position => {
+                    let conn = &mut self.connections[pending.conn_idx];
+                    let _ = write!(conn, "{}\r\n", position.refusal());
+                }
                 Some(command) => {
-                    let _ = (command)(pending.conn_idx, pending.at_room, &pending.arguments, self);
+                    let _ = (command)(pending.conn_idx, at_room, &pending.arguments, self);
                 }
                 None => {
                     let conn = &mut self.connections[pending.conn_idx];
@@ -143,6 +464,622 @@ impl World {
         }
     }
 
+    /// Sets the calendar, and picks some weather to go with the season.
+    pub fn start_clock(&mut self, time: GameTime) {
+        self.time = time;
+        for area in &mut self.areas {
+            area.weather = Weather::new(time.is_winter());
+        }
+    }
+
+    /// Runs once per game hour.
+    pub fn tick(&mut self) {
+        if let Some(sunlight) = self.time.advance() {
+            let message = match sunlight {
+                Sunlight::Rise => "The day has begun.",
+                Sunlight::Light => "The sun rises in the east.",
+                Sunlight::Set => "The sun slowly disappears in the west.",
+                Sunlight::Dark => "The night has begun.",
+            };
+            self.msg_outdoors(message, None);
+        }
+        let winter = self.time.is_winter();
+        for area_idx in 0..self.areas.len() {
+            if let Some(message) = self.areas[area_idx].weather.update(winter) {
+                self.msg_outdoors(message, Some(area_idx));
+            }
+        }
+        for area_idx in 0..self.areas.len() {
+            if self.areas[area_idx].age() {
+                self.reset_area(area_idx);
+            }
+        }
+        self.burn_lights();
+        self.decay_portals();
+        self.rot_corpses();
+        self.tick_object_effects();
+        self.tick_characters();
+    }
+
+    fn decay_portals(&mut self) {
+        let mut faded = vec![];
+        for (room_id, objs) in &self.room_objs {
+            for obj in objs.iter() {
+                if obj.portal().is_some_and(|portal| portal.decay()) {
+                    faded.push((*room_id, obj.instance()));
+                }
+            }
+        }
+        for (room_id, portal) in faded {
+            self.fade_portal(room_id, portal);
+        }
+    }
+
+    /// Takes a portal that's run out of charges or time out of its room and the world.
+    pub fn fade_portal(&mut self, room_id: RoomId, portal: InstanceId) {
+        let objs = self
+            .room_objs
+            .get_mut(&room_id)
+            .expect("Unwrapped None room objs");
+        let ptr = match objs.iter().find(|obj| obj.instance() == portal) {
+            Some(obj) => obj as *const Object,
+            None => return,
+        };
+        // SAFETY: the pointer was just found in this list.
+        let obj = unsafe { objs.cursor_mut_from_ptr(ptr) }.remove().unwrap();
+        self.msg_char(
+            &format!("{} fades away.", util::capitalize(obj.name())),
+            Recipient::All(room_id),
+        );
+        self.extract_object(&obj);
+    }
+
+    // Corpses left lying around rot away, and spill whatever was on them.
+    fn rot_corpses(&mut self) {
+        let mut rotted = vec![];
+        for (room_id, objs) in &self.room_objs {
+            for obj in objs.iter() {
+                if obj.rot() {
+                    rotted.push((*room_id, obj as *const Object));
+                }
+            }
+        }
+        for (room_id, ptr) in rotted {
+            let objs = self
+                .room_objs
+                .get_mut(&room_id)
+                .expect("Unwrapped None room objs");
+            // SAFETY: the pointer was found in this list, and nothing has left it since.
+            let obj = unsafe { objs.cursor_mut_from_ptr(ptr) }.remove().unwrap();
+            let mut contents = obj.contents.borrow_mut().take();
+            while let Some(inner) = contents.pop_front() {
+                objs.push_front(inner);
+            }
+            self.msg_char(
+                &format!("{} rots away.", util::capitalize(obj.name())),
+                Recipient::All(room_id),
+            );
+            self.extract_object(&obj);
+        }
+    }
+
+    // Effects on objects wear off, wherever the objects are. Only someone holding
+    // one, or anyone in the room with it, notices.
+    fn tick_object_effects(&mut self) {
+        let mut ended = vec![];
+        for obj in self.objects.iter() {
+            for kind in obj.tick_effects() {
+                ended.push((obj.instance(), kind));
+            }
+        }
+        for (instance, kind) in ended {
+            let held = self.characters.iter().find_map(|(char_idx, char)| {
+                char.inventory
+                    .iter()
+                    .chain(char.equipment.iter().map(|(_, obj)| &**obj))
+                    .find(|obj| obj.instance() == instance)
+                    .map(|obj| (obj.name().to_string(), Recipient::Subject(char_idx)))
+            });
+            let lying = || {
+                self.room_objs.iter().find_map(|(room_id, objs)| {
+                    objs.iter()
+                        .find(|obj| obj.instance() == instance)
+                        .map(|obj| (obj.name().to_string(), Recipient::All(*room_id)))
+                })
+            };
+            match held.or_else(lying) {
+                Some((_, Recipient::Subject(char_idx)))
+                    if self.characters[char_idx].connection().is_none() => {}
+                Some((name, recipient)) => {
+                    let message =
+                        format!("{} {}", util::capitalize(&name), kind.object_end_message());
+                    self.msg_char(&message, recipient);
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Where a portal leads right now, if anywhere. A paired portal leads to
+    /// whichever room its twin is in.
+    pub fn portal_destination(&self, portal: &Portal) -> Option<RoomId> {
+        if let Some(pair) = portal.pair {
+            return self
+                .room_objs
+                .iter()
+                .find(|(_, objs)| objs.iter().any(|obj| obj.id() == pair))
+                .map(|(room_id, _)| *room_id);
+        }
+        if let Some(to) = portal.to {
+            return Some(to).filter(|to| self.rooms.contains_key(to));
+        }
+        let rooms = &self
+            .areas
+            .iter()
+            .find(|area| Some(&area.file_name) == portal.to_area.as_ref())?
+            .rooms;
+        if rooms.is_empty() {
+            return None;
+        }
+        Some(rooms[util::random_range(0, rooms.len() as u32 - 1) as usize])
+    }
+
+    // Hunger, thirst, and effects wearing off. Npcs never go hungry.
+    fn tick_characters(&mut self) {
+        let mut messages = vec![];
+        for (char_idx, char) in &mut self.characters {
+            if char.is_player() {
+                for complaint in char.condition_mut().tick() {
+                    messages.push((char_idx, complaint));
+                }
+            }
+            if char.has_effect(EffectKind::Poison) {
+                char.condition_mut().nourish(-1, -1);
+                messages.push((char_idx, "You shiver and suffer."));
+            }
+            char.regenerate();
+            for kind in char.tick_effects() {
+                messages.push((char_idx, kind.end_message()));
+            }
+        }
+        for (char_idx, message) in messages {
+            if self.characters[char_idx].connection().is_some() {
+                self.msg_char(message, Recipient::Subject(char_idx));
+            }
+        }
+    }
+
+    /// Runs every pulse, to count down how long characters have to wait.
+    pub fn decrement_lag(&mut self) {
+        for (_, char) in &mut self.characters {
+            char.decrement_lag();
+        }
+    }
+
+    /// Runs every few seconds. Everyone in a fight gets a round of attacks.
+    pub fn violence(&mut self) {
+        let fights: Vec<(Index, Index)> = self
+            .characters
+            .iter()
+            .filter_map(|(idx, char)| char.fighting().map(|victim| (idx, victim)))
+            .collect();
+        for (char_idx, victim_idx) in fights {
+            // Someone may have died or fled earlier in the round.
+            let char = match self.characters.get(char_idx) {
+                Some(char) if char.fighting() == Some(victim_idx) => char,
+                _ => continue,
+            };
+            let in_room = char.in_room();
+            if self
+                .characters
+                .get(victim_idx)
+                .is_none_or(|victim| victim.in_room() != in_room)
+            {
+                // A duel with someone who's left is off.
+                self.stop_fighting(char_idx, false);
+                self.characters[char_idx].set_duelling(None);
+                continue;
+            }
+            self.attack(char_idx, victim_idx);
+        }
+    }
+
+    /// Starts a fight. Whoever's attacked fights back, unless they're already
+    /// busy with someone else.
+    pub fn start_fighting(&mut self, char_idx: Index, victim_idx: Index) {
+        self.characters[char_idx].set_fighting(Some(victim_idx));
+        let victim = &mut self.characters[victim_idx];
+        if victim.fighting().is_none() {
+            victim.set_fighting(Some(char_idx));
+        }
+    }
+
+    /// Stops a character fighting. With `everyone`, anyone fighting them stops too.
+    pub fn stop_fighting(&mut self, char_idx: Index, everyone: bool) {
+        for (idx, char) in &mut self.characters {
+            if idx == char_idx || (everyone && char.fighting() == Some(char_idx)) {
+                char.set_fighting(None);
+            }
+        }
+    }
+
+    /// One swing at someone, hit or miss.
+    pub fn attack(&mut self, char_idx: Index, victim_idx: Index) {
+        let char = &self.characters[char_idx];
+        let victim = &self.characters[victim_idx];
+        let attributes = char.attributes();
+        let damage = if combat::hits(&attributes, char.hit_bonus(), &victim.attributes()) {
+            combat::roll_damage(&attributes, char.equipment.wielded().is_some())
+        } else {
+            0
+        };
+        self.hit(char_idx, victim_idx, damage, None);
+    }
+
+    /// Lets everyone know how an attack went, named or not, then deals its damage.
+    /// Anyone who wasn't fighting yet is now.
+    pub fn hit(&mut self, char_idx: Index, victim_idx: Index, damage: u32, attack: Option<&str>) {
+        let char = &self.characters[char_idx];
+        let victim = &self.characters[victim_idx];
+        let room_id = char.in_room();
+        let (to_char, to_victim, to_room) =
+            combat::damage_messages(damage, attack, char.formal_name(), victim.formal_name());
+        let (char_connected, victim_connected) =
+            (char.connection().is_some(), victim.connection().is_some());
+        if char_connected {
+            self.msg_char(&to_char, Recipient::Subject(char_idx));
+        }
+        if victim_connected {
+            self.msg_char(&to_victim, Recipient::Subject(victim_idx));
+        }
+        self.msg_char(&to_room, Recipient::Neither(char_idx, victim_idx, room_id));
+        if self.characters[char_idx].fighting().is_none() {
+            self.characters[char_idx].set_fighting(Some(victim_idx));
+        }
+        if self.characters[victim_idx].fighting().is_none() {
+            self.characters[victim_idx].set_fighting(Some(char_idx));
+        }
+        self.damage(char_idx, victim_idx, damage);
+    }
+
+    /// Takes hit points off a character, and kills them if that's all of them.
+    /// A duellist who's beaten down far enough yields instead.
+    pub fn damage(&mut self, char_idx: Index, victim_idx: Index, damage: u32) {
+        let victim = &mut self.characters[victim_idx];
+        let duelling = victim.duelling() == Some(char_idx);
+        let vitals = victim.vitals_mut();
+        vitals.hp = vitals.hp.saturating_sub(damage);
+        if duelling && combat::must_yield(vitals.hp, vitals.max_hp) {
+            vitals.hp = vitals.hp.max(1);
+            let room_id = victim.in_room();
+            let victim_name = victim.formal_name().to_string();
+            let char_name = self.characters[char_idx].formal_name().to_string();
+            if self.characters[victim_idx].connection().is_some() {
+                self.msg_char(
+                    &format!("Beaten, you yield to {}.", char_name),
+                    Recipient::Subject(victim_idx),
+                );
+            }
+            self.msg_char(
+                &format!("Beaten, {} yields to {}!", victim_name, char_name),
+                Recipient::NotSubject(victim_idx, room_id),
+            );
+            self.end_duel(char_idx, victim_idx);
+        } else if vitals.hp == 0 {
+            self.die(victim_idx, char_idx);
+        }
+    }
+
+    /// Settles a duel. Nobody dies, but both of them remember how it went, and so
+    /// does everyone who watched.
+    pub fn end_duel(&mut self, winner_idx: Index, loser_idx: Index) {
+        self.stop_fighting(winner_idx, true);
+        self.stop_fighting(loser_idx, true);
+        let winner_name = self.characters[winner_idx].formal_name().to_string();
+        let loser_name = self.characters[loser_idx].formal_name().to_string();
+        let room_id = self.characters[winner_idx].in_room();
+        for (char_idx, rival, won) in &[
+            (winner_idx, &loser_name, true),
+            (loser_idx, &winner_name, false),
+        ] {
+            let char = &mut self.characters[*char_idx];
+            char.set_duelling(None);
+            let conn = match char
+                .connection()
+                .and_then(|idx| self.connections.get_mut(idx))
+            {
+                Some(conn) => conn,
+                None => continue,
+            };
+            let duels = conn.player_mut().duels_mut();
+            if *won {
+                duels.record_win(rival);
+                let _ = write!(conn, "You have won the duel against {}!\r\n", rival);
+            } else {
+                duels.record_loss(rival);
+                let _ = write!(conn, "You have lost the duel against {}.\r\n", rival);
+            }
+        }
+        self.msg_char(
+            &format!("{} has won the duel against {}!", winner_name, loser_name),
+            Recipient::Neither(winner_idx, loser_idx, room_id),
+        );
+    }
+
+    // Everything the victim had goes into their corpse, except their gold, which
+    // goes to whoever killed them. Npcs are gone until their area resets, and
+    // players wake up back at the start.
+    fn die(&mut self, victim_idx: Index, killer_idx: Index) {
+        self.stop_fighting(victim_idx, true);
+        if let Some(rival) = self.characters[victim_idx]
+            .duelling()
+            .and_then(|idx| self.characters.get_mut(idx))
+        {
+            rival.set_duelling(None);
+        }
+        self.characters[victim_idx].set_duelling(None);
+        let victim = &mut self.characters[victim_idx];
+        let room_id = victim.in_room();
+        let name = victim.formal_name().to_string();
+        let corpse = Rc::new(Object::corpse(&name, victim.keywords()));
+        {
+            let mut contents = corpse.contents.borrow_mut();
+            while let Some(obj) = victim.inventory.pop_front() {
+                contents.push_back(obj);
+            }
+            for obj in victim.equipment.take_all() {
+                contents.push_back(obj);
+            }
+        }
+        let gold = victim.gold();
+        victim.spend_gold(gold);
+        let is_player = victim.is_player();
+        let victim_level = victim.level();
+        let victim_conn = victim.connection();
+
+        self.msg_char(
+            &format!("{} is DEAD!!", util::capitalize(&name)),
+            Recipient::NotSubject(victim_idx, room_id),
+        );
+        if victim_conn.is_some() {
+            self.msg_char("You have been KILLED!!", Recipient::Subject(victim_idx));
+        }
+        self.objects.push_front(Rc::clone(&corpse));
+        self.room_objs
+            .get_mut(&room_id)
+            .expect("Unwrapped None room objs")
+            .push_front(corpse);
+        if !is_player {
+            let exp = character::kill_exp(self.characters[killer_idx].level(), victim_level);
+            self.gain_exp(killer_idx, exp);
+        }
+        if gold > 0 {
+            let killer = &mut self.characters[killer_idx];
+            killer.gain_gold(gold);
+            if killer.connection().is_some() {
+                self.msg_char(
+                    &format!("You take {} gold from {}.", gold, name),
+                    Recipient::Subject(killer_idx),
+                );
+            }
+        }
+
+        self.char_from_room(victim_idx, room_id);
+        if !is_player {
+            self.characters.remove(victim_idx);
+            return;
+        }
+        let victim = &mut self.characters[victim_idx];
+        victim.vitals_mut().hp = 1;
+        victim.set_position(Position::Resting);
+        self.char_to_room(victim_idx, RoomId::default());
+        if let Some(conn_idx) = victim_conn {
+            let _ = util::look_room(conn_idx, RoomId::default(), self);
+        }
+    }
+
+    /// Rewards a player with experience, for a kill, a quest, or anything else.
+    /// Npcs don't learn from experience.
+    pub fn gain_exp(&mut self, char_idx: Index, amount: u32) {
+        let char = match self.characters.get_mut(char_idx) {
+            Some(char) if char.is_player() && amount > 0 => char,
+            _ => return,
+        };
+        let levels = char.gain_exp(amount);
+        let level = char.level();
+        if char.connection().is_none() {
+            return;
+        }
+        let noun = if amount == 1 { "point" } else { "points" };
+        self.msg_char(
+            &format!("You receive {} experience {}.", amount, noun),
+            Recipient::Subject(char_idx),
+        );
+        if levels > 0 {
+            self.msg_char(
+                &format!("You raise a level!! You are now level {}.", level),
+                Recipient::Subject(char_idx),
+            );
+        }
+    }
+
+    /// Gives a player a little experience the first time they set foot in a room.
+    pub fn explore(&mut self, char_idx: Index, room_id: RoomId) {
+        let first_visit = self
+            .characters
+            .get(char_idx)
+            .and_then(|char| char.connection())
+            .and_then(|conn_idx| self.connections.get_mut(conn_idx))
+            .is_some_and(|conn| conn.player_mut().explore(room_id));
+        if first_visit {
+            self.gain_exp(char_idx, EXPLORE_EXP);
+        }
+    }
+
+    /// Takes a used up object out of the world for good, along with anything
+    /// inside it. It should already be out of any room, inventory, or container.
+    pub fn extract_object(&mut self, obj: &Object) {
+        let mut doomed = vec![obj as *const Object];
+        obj.for_each_content(&mut |inner| doomed.push(&**inner as *const Object));
+        for ptr in doomed {
+            // SAFETY: `world.objects` is the only list objects' `all_objs_link`s are
+            // ever put in, so a linked object is in this list.
+            if unsafe { &*ptr }.is_in_world() {
+                unsafe { self.objects.cursor_mut_from_ptr(ptr) }.remove();
+            }
+        }
+    }
+
+    fn reset_area(&mut self, area_idx: usize) {
+        // Anyone who was killed comes back.
+        let dead: Vec<CharId> = self.areas[area_idx]
+            .npcs
+            .iter()
+            .filter(|id| self.npc_defs.contains_key(id))
+            .filter(|id| !self.characters.iter().any(|(_, char)| char.id() == **id))
+            .copied()
+            .collect();
+        for id in dead {
+            self.spawn_npc(id);
+        }
+        let area_npcs = &self.areas[area_idx].npcs;
+        let keepers: Vec<Index> = self
+            .characters
+            .iter()
+            .filter(|(_, char)| char.shop().is_some() && area_npcs.contains(&char.id()))
+            .map(|(idx, _)| idx)
+            .collect();
+        for char_idx in keepers {
+            self.restock(char_idx);
+        }
+        // Hidden exits have to be found all over again.
+        for room_id in &self.areas[area_idx].rooms {
+            if let Some(room) = self.rooms.get_mut(room_id) {
+                for exit in room.exits.0.iter_mut() {
+                    exit.revealed = false;
+                }
+            }
+        }
+    }
+
+    /// Gives a shopkeeper one of anything in their stock that they've run out of.
+    pub fn restock(&mut self, char_idx: Index) {
+        let stock = match self.characters.get(char_idx).and_then(|char| char.shop()) {
+            Some(shop) => shop.stock.clone(),
+            None => return,
+        };
+        let char = &mut self.characters[char_idx];
+        for id in stock {
+            if char.inventory.iter().any(|obj| obj.id() == id) {
+                continue;
+            }
+            if let Some(def) = self.object_defs.get(&id) {
+                let obj = Rc::new(Object::from_prototype(def));
+                self.objects.push_front(Rc::clone(&obj));
+                char.inventory.push_front(obj);
+            }
+        }
+    }
+
+    // Tells everyone who's outside, in one area or everywhere.
+    fn msg_outdoors(&mut self, message: &str, area: Option<usize>) {
+        let chars: Vec<Index> = self
+            .rooms
+            .values()
+            .filter(|room| room.is_outdoors() && (area.is_none() || area == Some(room.area)))
+            .flat_map(|room| self.room_watchers(room.id))
+            .filter(|idx| {
+                self.characters
+                    .get(*idx)
+                    .and_then(|char| char.connection())
+                    .is_some()
+            })
+            .collect();
+        for char_idx in chars {
+            self.msg_char(message, Recipient::Subject(char_idx));
+        }
+    }
+
+    fn burn_lights(&mut self) {
+        let mut messages = vec![];
+        for (char_idx, char) in &self.characters {
+            if let Some(obj) = char.equipment.light().filter(|obj| obj.burn()) {
+                messages.push((
+                    format!("{} flickers and goes out.", util::capitalize(obj.name())),
+                    Recipient::Subject(char_idx),
+                ));
+            }
+        }
+        for (room_id, objs) in &self.room_objs {
+            for obj in objs.iter().filter(|obj| obj.burn()) {
+                messages.push((
+                    format!("{} flickers and goes out.", util::capitalize(obj.name())),
+                    Recipient::All(*room_id),
+                ));
+            }
+        }
+        for (message, recipient) in messages {
+            match recipient {
+                Recipient::Subject(char_idx)
+                    if self.characters[char_idx].connection().is_none() => {}
+                _ => self.msg_char(&message, recipient),
+            }
+        }
+    }
+
+    pub fn room_is_dark(&self, room_id: RoomId) -> bool {
+        let room = match self.rooms.get(&room_id) {
+            Some(room) => room,
+            None => return true,
+        };
+        let lit_by_char = self.room_chars[&room_id].iter().any(|idx| {
+            self.characters
+                .get(*idx)
+                .and_then(|char| char.equipment.light())
+                .is_some_and(|obj| obj.is_lit())
+        });
+        if lit_by_char || self.room_objs[&room_id].iter().any(|obj| obj.is_lit()) {
+            return false;
+        }
+        if room.has_flag(RoomFlag::Dark) {
+            return true;
+        }
+        if !room.is_outdoors() || room.sector == SectorType::City {
+            return false;
+        }
+        !self.time.sunlight().is_daylight()
+    }
+
+    fn is_admin(&self, char_idx: Index) -> bool {
+        self.characters
+            .get(char_idx)
+            .and_then(|char| char.connection())
+            .and_then(|conn_idx| self.connections.get(conn_idx))
+            .is_some_and(|conn| conn.player().is_admin())
+    }
+
+    /// Whether a character can make out anything in a room. Admins can always see.
+    pub fn can_see_room(&self, char_idx: Index, room_id: RoomId) -> bool {
+        let infravision = self
+            .characters
+            .get(char_idx)
+            .is_some_and(|char| char.has_effect(EffectKind::Infravision));
+        self.is_admin(char_idx) || infravision || !self.room_is_dark(room_id)
+    }
+
+    /// Whether one character can see another, who might be invisible.
+    pub fn can_see_char(&self, viewer_idx: Index, target_idx: Index) -> bool {
+        let invisible = self
+            .characters
+            .get(target_idx)
+            .is_some_and(|char| char.has_effect(EffectKind::Invisible));
+        let detects = self
+            .characters
+            .get(viewer_idx)
+            .is_some_and(|char| char.has_effect(EffectKind::DetectInvisible));
+        !invisible || detects || viewer_idx == target_idx || self.is_admin(viewer_idx)
+    }
+
     pub fn char_from_room(&mut self, char_idx: Index, from_room: RoomId) {
         let in_room = self
             .room_chars
@@ -166,6 +1103,7 @@ impl World {
             .get_mut(char_idx)
             .expect("Unwrapped None character");
         char.set_in_room(to_room);
+        char.set_furniture(None);
         // Add char index to new room
         if let Some(in_room) = self.room_chars.get_mut(&char.in_room()) {
             in_room.push(char_idx);
@@ -174,6 +1112,19 @@ impl World {
         }
     }
 
+    // Everyone in a room who'd notice something happening there. Sleepers miss it all.
+    fn room_watchers(&self, room_id: RoomId) -> Vec<Index> {
+        self.room_chars[&room_id]
+            .iter()
+            .copied()
+            .filter(|idx| {
+                self.characters
+                    .get(*idx)
+                    .is_some_and(|char| char.position().is_awake())
+            })
+            .collect()
+    }
+
     pub fn msg_char(&mut self, message: &str, recipient: Recipient) {
         match recipient {
             Recipient::Subject(char_idx) => {
@@ -193,9 +1144,8 @@ impl World {
                 }
             }
             Recipient::NotSubject(subj_index, room_id) => {
-                // FIXME: I don't like this .clone() here, not one bit
-                for char_idx in self.room_chars[&room_id]
-                    .clone()
+                for char_idx in self
+                    .room_watchers(room_id)
                     .iter()
                     .filter(|idx| **idx != subj_index)
                 {
@@ -216,9 +1166,8 @@ impl World {
                 }
             }
             Recipient::Neither(char_idx1, char_idx2, room_id) => {
-                // FIXME: I don't like this .clone() here, not one bit
-                for char_idx in self.room_chars[&room_id]
-                    .clone()
+                for char_idx in self
+                    .room_watchers(room_id)
                     .iter()
                     .filter(|&&idx| idx != char_idx1 && idx != char_idx2)
                 {
@@ -239,8 +1188,7 @@ impl World {
                 }
             }
             Recipient::All(room_id) => {
-                // FIXME: I don't like this .clone() here, not one bit
-                for char_idx in self.room_chars[&room_id].clone() {
+                for char_idx in self.room_watchers(room_id) {
                     if let Some(conn) = self
                         .characters
                         .get(char_idx)
@@ -269,6 +1217,56 @@ pub enum Recipient {
     All(RoomId),
 }
 
+#[derive(Debug, Default)]
+pub struct AreaReload {
+    pub rooms_added: usize,
+    pub rooms_updated: usize,
+    pub rooms_removed: usize,
+    pub objects: usize,
+    pub npcs: usize,
+    pub chars_relocated: usize,
+    // Npcs placed in rooms that don't exist, and objects loaded that don't
+    pub skipped_npcs: Vec<CharId>,
+    pub skipped_objects: Vec<ObjectId>,
+}
+
+impl Display for AreaReload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} rooms updated, {} added, {} removed; {} object and {} npc prototypes; {} characters relocated",
+            self.rooms_updated,
+            self.rooms_added,
+            self.rooms_removed,
+            self.objects,
+            self.npcs,
+            self.chars_relocated
+        )?;
+        if !self.skipped_npcs.is_empty() {
+            write!(
+                f,
+                "; skipped npcs in nonexistant rooms: {}",
+                join_ids(&self.skipped_npcs)
+            )?;
+        }
+        if !self.skipped_objects.is_empty() {
+            write!(
+                f,
+                "; skipped nonexistant objects: {}",
+                join_ids(&self.skipped_objects)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn join_ids<T: Display>(ids: &[T]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn load_areas() -> (
     Vec<Area>,
     HashMap<CharId, CharacterData, RandomState>,
@@ -297,10 +1295,12 @@ fn load_areas() -> (
                 if npcs.contains_key(&ch.id()) {
                     log::warn!("Loading areas: clobbered existing NPC {}", ch.id());
                 }
+                area.npcs.push(ch.id());
                 npcs.insert(ch.id(), ch);
             }
 
             for obj_def in area_objects {
+                area.objects.push(obj_def.id);
                 object_defs.insert(obj_def.id, obj_def);
             }
 
@@ -349,3 +1349,43 @@ fn audit_room_exits(rooms: &mut HashMap<RoomId, Room, RandomState>) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::World;
+    use crate::area::Area;
+    use crate::character::{CharId, CharacterData};
+    use crate::room::{Room, RoomId};
+
+    #[test]
+    fn reset_skips_npcs_in_missing_rooms() {
+        let mut world = World::default();
+        let room_id = RoomId::from(1);
+        world.rooms.insert(
+            room_id,
+            Room {
+                id: room_id,
+                ..Default::default()
+            },
+        );
+        world.room_chars.insert(room_id, vec![]);
+        let mut area = Area::default();
+        for &(id, in_room) in &[(1, 1), (2, 999)] {
+            let id = CharId::from(id);
+            area.npcs.push(id);
+            world.npc_defs.insert(
+                id,
+                CharacterData {
+                    id,
+                    in_room: RoomId::from(in_room),
+                    ..Default::default()
+                },
+            );
+        }
+        world.areas.push(area);
+
+        world.reset_area(0);
+        assert_eq!(world.characters.len(), 1);
+        assert_eq!(world.room_chars[&room_id].len(), 1);
+    }
+}